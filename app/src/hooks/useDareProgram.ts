@@ -10,7 +10,10 @@ import toast from 'react-hot-toast';
 // Import the IDL (this would be generated by Anchor)
 import idl from '@/lib/idl.json';
 
-const PROGRAM_ID = new PublicKey('11111111111111111111111111111111'); // Placeholder - replace with actual program ID after deployment
+const PROGRAM_ID = new PublicKey(idl.metadata.address);
+
+const [CONFIG] = PublicKey.findProgramAddressSync([Buffer.from('config')], PROGRAM_ID);
+const [TREASURY] = PublicKey.findProgramAddressSync([Buffer.from('treasury')], PROGRAM_ID);
 
 export const useDareProgram = () => {
   const { connection } = useConnection();
@@ -113,19 +116,36 @@ export const useDareProgram = () => {
         PROGRAM_ID
       );
 
+      // Betting and proofs both close at the deadline, then the admin has the
+      // config's resolution window to resolve
+      const { resolutionWindowSecs } = (await program.account.programConfig.fetch(CONFIG)) as any;
+      const deadline = new BN(params.deadline);
+
       const tx = await program.methods
         .createDare(
           params.title,
           params.description,
-          new BN(params.deadline),
+          {
+            bettingClosesAt: deadline,
+            proofDeadline: deadline,
+            resolutionDeadline: deadline.add(resolutionWindowSecs),
+          },
           new BN(params.minBet),
-          publicKey // platform_authority - for now same as creator
+          null, // feeOverride: the config's default fees
+          { admin: {} }, // resolutionMode
+          { admin: {} } // rankingMode
         )
         .accounts({
           dare: darePublicKey,
           poolAccount,
+          mint: null,
+          vault: null,
+          allowedMint: null,
+          committee: null,
+          config: CONFIG,
           creator: publicKey,
           systemProgram: SystemProgram.programId,
+          tokenProgram: null,
           rent: SYSVAR_RENT_PUBKEY,
         })
         .rpc();
@@ -185,6 +205,12 @@ export const useDareProgram = () => {
           dare: params.darePublicKey,
           bet: betPublicKey,
           poolAccount,
+          mint: null,
+          vault: null,
+          tokenProgram: null,
+          bettorTokens: null,
+          allowedMint: null,
+          config: CONFIG,
           bettor: publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -206,11 +232,23 @@ export const useDareProgram = () => {
     }
 
     try {
+      // Submissions are indexed by the dare's submission count
+      const { submissionCount } = (await program.account.dare.fetch(params.darePublicKey)) as any;
+      const index = Buffer.alloc(4);
+      index.writeUInt32LE(submissionCount);
+      const [proofSubmission] = PublicKey.findProgramAddressSync(
+        [Buffer.from('proof'), params.darePublicKey.toBuffer(), index],
+        PROGRAM_ID
+      );
+
       const tx = await program.methods
         .submitProof(params.proofHash, params.proofDescription)
         .accounts({
           dare: params.darePublicKey,
+          proofSubmission,
+          config: CONFIG,
           submitter: publicKey,
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
@@ -246,6 +284,11 @@ export const useDareProgram = () => {
           dare: darePublicKey,
           bet: betPublicKey,
           poolAccount,
+          mint: null,
+          vault: null,
+          tokenProgram: null,
+          winnerTokens: null,
+          config: CONFIG,
           winner: publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -260,7 +303,7 @@ export const useDareProgram = () => {
     }
   };
 
-  const claimCompletionReward = async (darePublicKey: PublicKey): Promise<boolean> => {
+  const claimWinnerReward = async (darePublicKey: PublicKey, proofSubmission: PublicKey): Promise<boolean> => {
     if (!program || !publicKey) {
       toast.error('Wallet not connected');
       return false;
@@ -273,20 +316,26 @@ export const useDareProgram = () => {
       );
 
       const tx = await program.methods
-        .claimCompletionReward()
+        .claimWinnerReward()
         .accounts({
           dare: darePublicKey,
+          proofSubmission,
           poolAccount,
-          submitter: publicKey,
+          mint: null,
+          vault: null,
+          tokenProgram: null,
+          winnerTokens: null,
+          config: CONFIG,
+          winner: publicKey,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
 
-      toast.success('Completion reward claimed successfully!');
+      toast.success('Winner reward claimed successfully!');
       return true;
     } catch (error) {
-      console.error('Error claiming completion reward:', error);
-      toast.error('Failed to claim completion reward');
+      console.error('Error claiming winner reward:', error);
+      toast.error('Failed to claim winner reward');
       return false;
     }
   };
@@ -314,17 +363,24 @@ export const useDareProgram = () => {
           dare: params.darePublicKey,
           bet: betPublicKey,
           poolAccount,
+          mint: null,
+          vault: null,
+          tokenProgram: null,
+          bettorTokens: null,
+          treasuryTokens: null,
+          config: CONFIG,
+          treasury: TREASURY,
           bettor: publicKey,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
 
-      toast.success('Cashed out successfully! (10% penalty applied)');
+      toast.success('Cashed out successfully! (early cash-out penalty applied)');
       return true;
     } catch (error: any) {
       console.error('Error cashing out:', error);
       if (error.message?.includes('CashOutTooLate')) {
-        toast.error('Cannot cash out within 10 minutes of deadline');
+        toast.error('Cash-outs are only possible within 10 minutes of your first bet');
       } else {
         toast.error('Failed to cash out');
       }
@@ -349,6 +405,11 @@ export const useDareProgram = () => {
         .accounts({
           dare: darePublicKey,
           poolAccount,
+          mint: null,
+          vault: null,
+          tokenProgram: null,
+          creatorTokens: null,
+          config: CONFIG,
           creator: publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -363,17 +424,24 @@ export const useDareProgram = () => {
     }
   };
 
-  const approveProof = async (darePublicKey: PublicKey, approve: boolean): Promise<boolean> => {
+  const approveProof = async (
+    darePublicKey: PublicKey,
+    proofSubmission: PublicKey,
+    approve: boolean,
+    reasonCode = 0
+  ): Promise<boolean> => {
     if (!program || !publicKey) {
       toast.error('Wallet not connected');
       return false;
     }
 
     try {
-      const tx = await program.methods
-        .approveProof(approve)
+      const method = approve ? program.methods.approveProof() : program.methods.rejectProof(reasonCode);
+      const tx = await method
         .accounts({
           dare: darePublicKey,
+          proofSubmission,
+          config: CONFIG,
           platformAuthority: publicKey,
         })
         .rpc();
@@ -396,7 +464,7 @@ export const useDareProgram = () => {
     placeBet,
     submitProof,
     claimWinnings,
-    claimCompletionReward,
+    claimWinnerReward,
     claimCreatorFee,
     cashOutEarly,
     approveProof,
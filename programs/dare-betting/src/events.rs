@@ -0,0 +1,357 @@
+//! Events emitted by every instruction that changes state.
+//!
+//! They are emitted with `emit_cpi!`, so they land in the transaction's inner
+//! instructions instead of its logs and can't be lost to log truncation. Each
+//! event carries the resulting values (pool totals, counts, status) alongside
+//! the delta, so an indexer can rebuild the accounts without re-fetching them.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    BetType, Dare, DareSchedule, DareStatus, DisputeConfig, FeeSchedule, PauseScope,
+    PendingActionKind, RankingMode, ResolutionMode,
+};
+
+#[event]
+pub struct ConfigInitialized {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub default_fees: FeeSchedule,
+    pub max_fees: FeeSchedule,
+    pub claim_window_secs: i64,
+    pub resolution_window_secs: i64,
+    pub disputes: DisputeConfig,
+    pub withdrawal_timelock_secs: i64,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminAccepted {
+    pub previous_admin: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct FeeScheduleUpdated {
+    pub default_fees: FeeSchedule,
+    pub max_fees: FeeSchedule,
+}
+
+#[event]
+pub struct ClaimWindowUpdated {
+    pub claim_window_secs: i64,
+}
+
+#[event]
+pub struct ResolutionWindowUpdated {
+    pub resolution_window_secs: i64,
+}
+
+/// A queued dispute config change was executed
+#[event]
+pub struct DisputeConfigUpdated {
+    pub action_id: u64,
+    pub disputes: DisputeConfig,
+}
+
+#[event]
+pub struct WithdrawalTimelockUpdated {
+    pub withdrawal_timelock_secs: i64,
+}
+
+#[event]
+pub struct PauseSet {
+    pub scope: PauseScope,
+    pub dare: Option<Pubkey>, // Set for PauseScope::Dare
+    pub flags: u8, // The scope's PAUSE_* bits after this call
+}
+
+#[event]
+pub struct ActionQueued {
+    pub pending_action: Pubkey,
+    pub action_id: u64,
+    pub kind: PendingActionKind,
+    pub queued_at: i64,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct ActionCancelled {
+    pub pending_action: Pubkey,
+    pub action_id: u64,
+}
+
+/// A queued withdrawal was executed
+#[event]
+pub struct TreasuryWithdrawn {
+    pub action_id: u64,
+    pub mint: Option<Pubkey>, // None for SOL
+    pub recipient: Pubkey, // Wallet for SOL, token account for tokens
+    pub amount: u64,
+}
+
+#[event]
+pub struct AllowedMintSet {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub fee_override: Option<FeeSchedule>,
+}
+
+#[event]
+pub struct AllowedMintRemoved {
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct CommitteeCreated {
+    pub committee: Pubkey,
+    pub committee_id: u64,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct DareCreated {
+    pub dare: Pubkey,
+    pub creator: Pubkey,
+    pub title: String,
+    pub description: String,
+    pub schedule: DareSchedule,
+    pub mint: Option<Pubkey>,
+    pub min_bet: u64,
+    pub fees: FeeSchedule,
+    pub resolution_mode: ResolutionMode,
+    pub ranking_mode: RankingMode,
+}
+
+#[event]
+pub struct BetPlaced {
+    pub dare: Pubkey,
+    pub bettor: Pubkey,
+    pub bet_type: BetType,
+    pub amount: u64,
+    pub position_will_do: u64, // The bettor's position after this bet
+    pub position_wont_do: u64,
+    pub will_do_pool: u64, // The dare's pools after this bet
+    pub wont_do_pool: u64,
+    pub total_pool: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CashedOut {
+    pub dare: Pubkey,
+    pub bettor: Pubkey,
+    pub stake: u64,
+    pub refund: u64,
+    pub penalty_to_pool: u64,
+    pub penalty_to_treasury: u64,
+    pub will_do_pool: u64, // The dare's pools after the cash-out
+    pub wont_do_pool: u64,
+    pub total_pool: u64,
+}
+
+#[event]
+pub struct ProofSubmitted {
+    pub dare: Pubkey,
+    pub proof_submission: Pubkey,
+    pub submitter: Pubkey,
+    pub submission_index: u32,
+    pub proof_hash: String,
+    pub proof_description: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProofApproved {
+    pub dare: Pubkey,
+    pub proof_submission: Pubkey,
+    pub reviewer: Pubkey,
+    pub approved_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProofRejected {
+    pub dare: Pubkey,
+    pub proof_submission: Pubkey,
+    pub reviewer: Pubkey,
+    pub reason_code: u8,
+    pub rejected_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProofLiked {
+    pub proof_submission: Pubkey,
+    pub liker: Pubkey,
+    pub likes_count: u32,
+}
+
+#[event]
+pub struct ProofUnliked {
+    pub proof_submission: Pubkey,
+    pub liker: Pubkey,
+    pub likes_count: u32,
+}
+
+/// From `select_winners` or `finalize_rankings`. Places without a submission are `None`.
+#[event]
+pub struct WinnersSelected {
+    pub dare: Pubkey,
+    pub first_place: Option<Pubkey>,
+    pub second_place: Option<Pubkey>,
+    pub third_place: Option<Pubkey>,
+}
+
+#[event]
+pub struct VoteCast {
+    pub dare: Pubkey,
+    pub voter: Pubkey,
+    pub outcome: BetType,
+    pub weight: u64,
+    pub will_do_weight: u64, // Running tallies after this vote
+    pub wont_do_weight: u64,
+}
+
+#[event]
+pub struct CommitteeVoteCast {
+    pub dare: Pubkey,
+    pub committee: Pubkey,
+    pub member: Pubkey,
+    pub outcome: BetType,
+    pub will_do_votes: u8, // Running tallies after this vote
+    pub wont_do_votes: u8,
+}
+
+/// The outcome was frozen, by whichever resolution path the dare uses
+#[event]
+pub struct DareResolved {
+    pub dare: Pubkey,
+    pub outcome: Option<BetType>,
+    pub status: DareStatus, // Completed, Failed, or Refunding if nobody bet on the winning side
+    pub settled_at: i64,
+    pub dispute_deadline: i64,
+}
+
+impl DareResolved {
+    pub fn new(dare: &Account<Dare>) -> Self {
+        Self {
+            dare: dare.key(),
+            outcome: dare.outcome.clone(),
+            status: dare.status,
+            settled_at: dare.settled_at,
+            dispute_deadline: dare.dispute_deadline,
+        }
+    }
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub ruling_deadline: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub overturned: bool,
+    pub outcome: Option<BetType>,
+    pub status: DareStatus,
+    pub reward: u64, // Paid from the dispute reserve when overturned
+    pub bond_slashed: u64, // Sent to the treasury and added to the reserve when upheld
+    pub dispute_reserve: u64, // The config's reserve after this ruling
+    pub timestamp: i64,
+}
+
+/// A dispute closed without a ruling, returning the bond
+#[event]
+pub struct DisputeExpired {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub outcome: Option<BetType>, // The original outcome, which stands
+    pub status: DareStatus,
+    pub bond_returned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WinningsClaimed {
+    pub dare: Pubkey,
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub winning_stake: u64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct WinnerRewardClaimed {
+    pub dare: Pubkey,
+    pub proof_submission: Pubkey,
+    pub winner: Pubkey,
+    pub rank: u8,
+    pub amount: u64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct CreatorFeeClaimed {
+    pub dare: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct ProtocolFeeCollected {
+    pub dare: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub dare: Pubkey,
+    pub bettor: Pubkey,
+    pub stake: u64,
+    pub refund: u64,
+    pub fee: u64,
+    pub refunded_stake: u64,
+}
+
+/// `closed` is set when an empty dare was closed outright instead of refunded
+#[event]
+pub struct DareCancelled {
+    pub dare: Pubkey,
+    pub authority: Pubkey,
+    pub closed: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DustSwept {
+    pub dare: Pubkey,
+    pub amount: u64,
+    pub forfeited_obligations: u64, // Unclaimed payouts forfeited after the claim window
+}
+
+/// Bettors claim their share of `pool` through `claim_refund`
+#[event]
+pub struct EmergencyRefundStarted {
+    pub dare: Pubkey,
+    pub admin: Pubkey,
+    pub pool: u64,
+    pub outstanding_stake: u64,
+    pub timestamp: i64,
+}
@@ -1,36 +1,522 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use std::str::FromStr;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+pub mod events;
+pub mod oracle;
+pub mod payout;
+pub mod pda;
+pub mod ranking;
+pub mod vault;
+
+use events::*;
+use vault::Pool;
 
 declare_id!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
 
+/// Basis points denominator (10_000 bps = 100%)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Pause bits for `ProgramConfig::paused` (every dare) and `Dare::paused` (one dare)
+pub const PAUSE_BETTING: u8 = 1 << 0; // create_dare, place_bet
+pub const PAUSE_PROOF_SUBMISSION: u8 = 1 << 1; // submit_proof
+pub const PAUSE_CLAIMING: u8 = 1 << 2; // Winnings, rewards, fees, refunds and sweep_dust
+pub const PAUSE_CASH_OUT: u8 = 1 << 3; // cash_out_early
+pub const PAUSE_ALL: u8 = PAUSE_BETTING | PAUSE_PROOF_SUBMISSION | PAUSE_CLAIMING | PAUSE_CASH_OUT;
+
 #[program]
 pub mod dare_betting {
     use super::*;
 
+    /// One-time setup of the global `ProgramConfig`. Only the program's upgrade
+    /// authority can call this, and the signer becomes the first admin.
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        default_fees: FeeSchedule,
+        max_fees: FeeSchedule,
+        claim_window_secs: i64,
+        resolution_window_secs: i64,
+        disputes: DisputeConfig,
+        withdrawal_timelock_secs: i64,
+    ) -> Result<()> {
+        max_fees.validate()?;
+        require!(default_fees.is_within(&max_fees), ErrorCode::FeeAboveLimit);
+        require!(claim_window_secs > 0, ErrorCode::InvalidClaimWindow);
+        require!(resolution_window_secs > 0, ErrorCode::InvalidResolutionWindow);
+        require!(withdrawal_timelock_secs > 0, ErrorCode::InvalidTimelock);
+        disputes.validate()?;
+
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.pending_admin = None;
+        config.treasury = ctx.accounts.treasury.key();
+        config.default_fees = default_fees;
+        config.max_fees = max_fees;
+        config.claim_window_secs = claim_window_secs;
+        config.resolution_window_secs = resolution_window_secs;
+        config.disputes = disputes;
+        config.withdrawal_timelock_secs = withdrawal_timelock_secs;
+        config.dispute_reserve = 0;
+        config.paused = 0;
+        config.claims_resumed_at = 0;
+        config.bump = ctx.bumps.config;
+
+        emit_cpi!(ConfigInitialized {
+            admin: config.admin,
+            treasury: config.treasury,
+            default_fees,
+            max_fees,
+            claim_window_secs,
+            resolution_window_secs,
+            disputes,
+            withdrawal_timelock_secs,
+        });
+
+        Ok(())
+    }
+
+    /// First step of admin rotation: the current admin nominates a successor
+    pub fn propose_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.config.pending_admin = Some(new_admin);
+
+        emit_cpi!(AdminProposed {
+            admin: ctx.accounts.admin.key(),
+            pending_admin: new_admin,
+        });
+
+        Ok(())
+    }
+
+    /// Updates the default fee schedule for new dares and the per-dare override limits.
+    /// Dares that already exist keep the schedule they were created with.
+    pub fn set_fee_schedule(
+        ctx: Context<UpdateConfig>,
+        default_fees: FeeSchedule,
+        max_fees: FeeSchedule,
+    ) -> Result<()> {
+        max_fees.validate()?;
+        require!(default_fees.is_within(&max_fees), ErrorCode::FeeAboveLimit);
+
+        let config = &mut ctx.accounts.config;
+        config.default_fees = default_fees;
+        config.max_fees = max_fees;
+
+        emit_cpi!(FeeScheduleUpdated { default_fees, max_fees });
+
+        Ok(())
+    }
+
+    /// How long after settlement claimants have before `sweep_dust` may forfeit
+    /// their unclaimed payouts to the treasury
+    pub fn set_claim_window(ctx: Context<UpdateConfig>, claim_window_secs: i64) -> Result<()> {
+        require!(claim_window_secs > 0, ErrorCode::InvalidClaimWindow);
+
+        ctx.accounts.config.claim_window_secs = claim_window_secs;
+
+        emit_cpi!(ClaimWindowUpdated { claim_window_secs });
+
+        Ok(())
+    }
+
+    /// Minimum time a new dare must leave between its proof deadline and its
+    /// resolution deadline, so the admin always gets a chance to `resolve_dare`
+    /// before anyone can resolve it as failed
+    pub fn set_resolution_window(
+        ctx: Context<UpdateConfig>,
+        resolution_window_secs: i64,
+    ) -> Result<()> {
+        require!(resolution_window_secs > 0, ErrorCode::InvalidResolutionWindow);
+
+        ctx.accounts.config.resolution_window_secs = resolution_window_secs;
+
+        emit_cpi!(ResolutionWindowUpdated { resolution_window_secs });
+
+        Ok(())
+    }
+
+    /// Queues a change of the arbiter, the dispute window and the bond and reward
+    /// amounts. The arbiter decides who gets dispute rewards, so the change waits
+    /// out the withdrawal timelock like any other treasury outflow. Dares that are
+    /// already resolved keep the dispute deadline they were given.
+    pub fn queue_dispute_config(
+        ctx: Context<QueueAction>,
+        action_id: u64,
+        disputes: DisputeConfig,
+    ) -> Result<()> {
+        disputes.validate()?;
+
+        queue_action(
+            ctx,
+            action_id,
+            PendingActionKind::DisputeConfigUpdate { disputes },
+        )
+    }
+
+    /// Lengthens the delay between queueing an admin action and executing it.
+    /// It can never be shortened, so users can rely on the notice they get.
+    pub fn set_withdrawal_timelock(
+        ctx: Context<UpdateConfig>,
+        withdrawal_timelock_secs: i64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        require!(
+            withdrawal_timelock_secs >= config.withdrawal_timelock_secs,
+            ErrorCode::InvalidTimelock
+        );
+
+        config.withdrawal_timelock_secs = withdrawal_timelock_secs;
+
+        emit_cpi!(WithdrawalTimelockUpdated { withdrawal_timelock_secs });
+
+        Ok(())
+    }
+
+    /// Pauses the instructions covered by the `PAUSE_*` bits set in `flags` and
+    /// resumes the rest, either for every dare or for the `dare` passed in. An
+    /// instruction is blocked when either scope has its bit set. Resuming claims
+    /// gives bettors a full claim window before `sweep_dust` can forfeit them.
+    pub fn set_paused(ctx: Context<SetPaused>, scope: PauseScope, flags: u8) -> Result<()> {
+        require!(flags & !PAUSE_ALL == 0, ErrorCode::InvalidPauseFlags);
+        let now = Clock::get()?.unix_timestamp;
+
+        let dare = match scope {
+            PauseScope::Global => {
+                let config = &mut ctx.accounts.config;
+                if resumes_claims(config.paused, flags) {
+                    config.claims_resumed_at = now;
+                }
+                config.paused = flags;
+                None
+            }
+            PauseScope::Dare => {
+                let dare = ctx.accounts.dare.as_mut().ok_or(ErrorCode::MissingPausedDare)?;
+                if resumes_claims(dare.paused, flags) {
+                    dare.claims_resumed_at = now;
+                }
+                dare.paused = flags;
+                Some(dare.key())
+            }
+        };
+
+        emit_cpi!(PauseSet { scope, dare, flags });
+
+        Ok(())
+    }
+
+    /// Queues a withdrawal of protocol revenue from the treasury PDA: SOL when
+    /// `mint` is `None`, otherwise from the treasury's token account for `mint`
+    /// to the token account `recipient`. It can only be executed once the
+    /// config's withdrawal timelock has passed, and the admin can cancel it until then.
+    pub fn queue_treasury_withdrawal(
+        ctx: Context<QueueAction>,
+        action_id: u64,
+        mint: Option<Pubkey>,
+        recipient: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        queue_action(
+            ctx,
+            action_id,
+            PendingActionKind::TreasuryWithdrawal { mint, recipient, amount },
+        )
+    }
+
+    /// Carries out a queued action once its timelock has passed. Permissionless:
+    /// the action already fixes what happens, and its rent goes back to the admin.
+    pub fn execute_pending_action(ctx: Context<ExecutePendingAction>) -> Result<()> {
+        let pending_action = &ctx.accounts.pending_action;
+        require!(
+            Clock::get()?.unix_timestamp >= pending_action.executable_at,
+            ErrorCode::TimelockNotElapsed
+        );
+
+        let (mint, recipient, amount) = match pending_action.kind {
+            PendingActionKind::TreasuryWithdrawal { mint, recipient, amount } => {
+                (mint, recipient, amount)
+            }
+            PendingActionKind::DisputeConfigUpdate { disputes } => {
+                ctx.accounts.config.disputes = disputes;
+
+                emit_cpi!(DisputeConfigUpdated {
+                    action_id: pending_action.action_id,
+                    disputes,
+                });
+
+                return Ok(());
+            }
+        };
+        let recipient_account = match &ctx.accounts.recipient {
+            Some(account) if account.key() == recipient => account.to_account_info(),
+            _ => return err!(ErrorCode::PendingActionMismatch),
+        };
+
+        let treasury = ctx.accounts.treasury.to_account_info();
+        match mint {
+            None => {
+                // The treasury always keeps its rent-exempt minimum and the
+                // slashed bonds set aside for dispute rewards
+                let reserved = payout::add(
+                    Rent::get()?.minimum_balance(0),
+                    ctx.accounts.config.dispute_reserve,
+                )?;
+                let available = treasury.lamports().saturating_sub(reserved);
+                require!(amount <= available, ErrorCode::InsufficientTreasuryFunds);
+
+                payout::transfer_from_pool(&treasury, &recipient_account, amount)?;
+            }
+            Some(mint) => {
+                let (Some(mint_account), Some(treasury_tokens), Some(token_program)) = (
+                    &ctx.accounts.mint,
+                    &ctx.accounts.treasury_tokens,
+                    &ctx.accounts.token_program,
+                ) else {
+                    return err!(ErrorCode::MissingTokenAccounts);
+                };
+                require_keys_eq!(mint_account.key(), mint, ErrorCode::MintMismatch);
+                require_keys_eq!(treasury_tokens.owner, treasury.key(), ErrorCode::InvalidTokenAccount);
+                require_keys_eq!(treasury_tokens.mint, mint, ErrorCode::MintMismatch);
+                require!(amount <= treasury_tokens.amount, ErrorCode::InsufficientTreasuryFunds);
+
+                let seeds: &[&[u8]] = &[b"treasury", &[ctx.bumps.treasury]];
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        TransferChecked {
+                            from: treasury_tokens.to_account_info(),
+                            mint: mint_account.to_account_info(),
+                            to: recipient_account,
+                            authority: treasury,
+                        },
+                        &[seeds],
+                    ),
+                    amount,
+                    mint_account.decimals,
+                )?;
+            }
+        }
+
+        emit_cpi!(TreasuryWithdrawn {
+            action_id: pending_action.action_id,
+            mint,
+            recipient,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Drops a queued action before it runs
+    pub fn cancel_pending_action(ctx: Context<CancelPendingAction>) -> Result<()> {
+        emit_cpi!(ActionCancelled {
+            pending_action: ctx.accounts.pending_action.key(),
+            action_id: ctx.accounts.pending_action.action_id,
+        });
+
+        Ok(())
+    }
+
+    /// Second step of admin rotation: the nominee signs to take over
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            config.pending_admin == Some(ctx.accounts.new_admin.key()),
+            ErrorCode::NotPendingAdmin
+        );
+
+        let previous_admin = config.admin;
+        config.admin = ctx.accounts.new_admin.key();
+        config.pending_admin = None;
+
+        emit_cpi!(AdminAccepted {
+            previous_admin,
+            admin: ctx.accounts.new_admin.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Adds a mint to the allowlist, or updates its limits. Token dares can only
+    /// be created and bet on in allowed mints, so the admin vets each token
+    /// (freeze authority, transfer hooks, fees) before listing it.
+    pub fn set_allowed_mint(
+        ctx: Context<SetAllowedMint>,
+        decimals: u8,
+        min_bet: u64,
+        max_bet: u64,
+        fee_override: Option<FeeSchedule>,
+    ) -> Result<()> {
+        require!(
+            decimals == ctx.accounts.mint.decimals && min_bet > 0 && min_bet <= max_bet,
+            ErrorCode::InvalidMintConfig
+        );
+        if let Some(fees) = &fee_override {
+            require!(fees.is_within(&ctx.accounts.config.max_fees), ErrorCode::FeeAboveLimit);
+        }
+
+        let allowed_mint = &mut ctx.accounts.allowed_mint;
+        allowed_mint.mint = ctx.accounts.mint.key();
+        allowed_mint.decimals = decimals;
+        allowed_mint.min_bet = min_bet;
+        allowed_mint.max_bet = max_bet;
+        allowed_mint.fee_override = fee_override;
+        allowed_mint.bump = ctx.bumps.allowed_mint;
+
+        emit_cpi!(AllowedMintSet {
+            mint: allowed_mint.mint,
+            decimals,
+            min_bet,
+            max_bet,
+            fee_override,
+        });
+
+        Ok(())
+    }
+
+    /// Delists a mint. Live dares in it stop taking bets; claims are unaffected.
+    pub fn remove_allowed_mint(ctx: Context<RemoveAllowedMint>) -> Result<()> {
+        emit_cpi!(AllowedMintRemoved {
+            mint: ctx.accounts.allowed_mint.mint,
+        });
+
+        Ok(())
+    }
+
+    /// Registers an M-of-N committee that dares can name as their resolver.
+    /// Membership and threshold are fixed once created. The threshold must be
+    /// a strict majority: the first side to reach it resolves the dare, so a
+    /// lower one would let a minority decide against the rest.
+    pub fn create_committee(
+        ctx: Context<CreateCommittee>,
+        committee_id: u64,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(
+            !members.is_empty() && members.len() <= ResolverCommittee::MAX_MEMBERS,
+            ErrorCode::InvalidCommittee
+        );
+        require!(
+            usize::from(threshold) * 2 > members.len() && usize::from(threshold) <= members.len(),
+            ErrorCode::InvalidCommittee
+        );
+        for (i, member) in members.iter().enumerate() {
+            require!(!members[..i].contains(member), ErrorCode::InvalidCommittee);
+        }
+
+        let committee = &mut ctx.accounts.committee;
+        committee.committee_id = committee_id;
+        committee.members = members;
+        committee.threshold = threshold;
+        committee.bump = ctx.bumps.committee;
+
+        emit_cpi!(CommitteeCreated {
+            committee: committee.key(),
+            committee_id,
+            members: committee.members.clone(),
+            threshold,
+        });
+
+        Ok(())
+    }
+
+    /// Creates a dare staked in SOL, or in an SPL Token / Token-2022 mint when
+    /// `mint`, `vault` and `token_program` are passed. The vault is created at
+    /// `[b"vault", dare]` with the pool PDA as its authority, and the mint must
+    /// be on the allowlist.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_dare(
         ctx: Context<CreateDare>,
         title: String,
         description: String,
-        deadline: i64,
+        schedule: DareSchedule,
         min_bet: u64,
+        fee_override: Option<FeeSchedule>,
+        resolution_mode: ResolutionMode,
+        ranking_mode: RankingMode,
     ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require!(config.paused & PAUSE_BETTING == 0, ErrorCode::Paused);
+
+        schedule.validate(Clock::get()?.unix_timestamp, config.resolution_window_secs)?;
+
+        // Token dares must use an allowed mint, which may bring its own default fees
+        let (mint, default_fees) = match (&ctx.accounts.mint, &ctx.accounts.vault) {
+            (Some(mint), Some(_)) => {
+                let allowed_mint =
+                    AllowedMint::require_for(&ctx.accounts.allowed_mint, mint.key())?;
+                require!(min_bet >= allowed_mint.min_bet, ErrorCode::BetTooLow);
+                require!(min_bet <= allowed_mint.max_bet, ErrorCode::BetTooHigh);
+                (
+                    Some(mint.key()),
+                    allowed_mint.fee_override.unwrap_or(config.default_fees),
+                )
+            }
+            (None, None) => (None, config.default_fees),
+            _ => return err!(ErrorCode::MissingTokenAccounts),
+        };
+
+        // Creators may pick their own schedule as long as it stays within the admin limits
+        let fees = match fee_override {
+            Some(mut fees) => {
+                require!(fees.is_within(&config.max_fees), ErrorCode::FeeAboveLimit);
+                // The platform's own cut is not up to the creator
+                fees.protocol_fee_bps = default_fees.protocol_fee_bps;
+                fees.cash_out_treasury_bps = default_fees.cash_out_treasury_bps;
+                fees.refund_fee_bps = default_fees.refund_fee_bps;
+                fees
+            }
+            None => default_fees,
+        };
+
+        // Committee dares must name a committee that exists
+        if let ResolutionMode::Committee { committee } = resolution_mode {
+            let account = ctx
+                .accounts
+                .committee
+                .as_ref()
+                .ok_or(ErrorCode::CommitteeMismatch)?;
+            require!(account.key() == committee, ErrorCode::CommitteeMismatch);
+        }
+
         let dare = &mut ctx.accounts.dare;
         dare.creator = ctx.accounts.creator.key();
-        // For now, set creator as platform authority. In production, this should be a dedicated admin key
-        dare.platform_authority = ctx.accounts.creator.key();
         dare.title = title;
         dare.description = description;
-        dare.deadline = deadline;
+        dare.betting_closes_at = schedule.betting_closes_at;
+        dare.proof_deadline = schedule.proof_deadline;
+        dare.resolution_deadline = schedule.resolution_deadline;
+        dare.mint = mint;
         dare.min_bet = min_bet;
+        dare.resolution_mode = resolution_mode;
+        dare.ranking_mode = ranking_mode;
+        dare.committee_will_do_votes = 0;
+        dare.committee_wont_do_votes = 0;
+        dare.community_will_do_weight = 0;
+        dare.community_wont_do_weight = 0;
+        // Snapshot the fees so later config changes don't affect live dares
+        dare.fees = fees;
         dare.total_pool = 0;
         dare.will_do_pool = 0;
         dare.wont_do_pool = 0;
-        dare.is_completed = false;
-        dare.is_expired = false;
+        dare.status = DareStatus::Open;
         dare.creator_fee_claimed = false;
+        dare.protocol_fee_collected = false;
+        dare.total_claimed = 0;
+        dare.winning_stake_claimed = 0;
+        dare.outcome = None;
+        dare.settled_at = 0;
+        dare.dispute_deadline = 0;
+        dare.paused = 0;
+        dare.refunded_stake = 0;
+        dare.emergency_pool = 0;
+        dare.emergency_stake = 0;
+        dare.claims_resumed_at = 0;
         dare.submission_count = 0;
-        dare.winners_selected = false;
+        dare.approved_count = 0;
+        dare.rejected_count = 0;
         dare.first_place_winner = None;
         dare.second_place_winner = None;
         dare.third_place_winner = None;
@@ -39,46 +525,91 @@ pub mod dare_betting {
         dare.third_place_claimed = false;
         dare.bump = ctx.bumps.dare;
         
+        emit_cpi!(DareCreated {
+            dare: dare.key(),
+            creator: dare.creator,
+            title: dare.title.clone(),
+            description: dare.description.clone(),
+            schedule,
+            mint,
+            min_bet,
+            fees,
+            resolution_mode: dare.resolution_mode,
+            ranking_mode,
+        });
+
         Ok(())
     }
 
+    /// Opens the bettor's position on the first call and adds to it afterwards.
+    /// Either side can be topped up, so a bettor may hedge across both.
     pub fn place_bet(
         ctx: Context<PlaceBet>,
         amount: u64,
         bet_type: BetType,
     ) -> Result<()> {
+        let pool = Pool::new(
+            &ctx.accounts.dare,
+            &ctx.accounts.pool_account,
+            ctx.bumps.pool_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+        )?;
         let dare = &mut ctx.accounts.dare;
         let bet = &mut ctx.accounts.bet;
         
+        dare.require_not_paused(&ctx.accounts.config, PAUSE_BETTING)?;
+        require!(!bet.is_claimed, ErrorCode::AlreadyClaimed);
         require!(amount >= dare.min_bet, ErrorCode::BetTooLow);
-        require!(Clock::get()?.unix_timestamp < dare.deadline, ErrorCode::DareExpired);
-        require!(!dare.is_completed, ErrorCode::DareAlreadyCompleted);
+        if let Some(mint) = dare.mint {
+            // Delisting a mint stops new bets, and positions are capped per mint
+            let allowed_mint = AllowedMint::require_for(&ctx.accounts.allowed_mint, mint)?;
+            require!(
+                payout::add(bet.total_amount()?, amount)? <= allowed_mint.max_bet,
+                ErrorCode::BetTooHigh
+            );
+        }
+        let now = Clock::get()?.unix_timestamp;
+        dare.refresh_status(now)?;
+        dare.require_status(&[DareStatus::Open])?;
 
-        // Transfer SOL from bettor to pool account
-        let cpi_context = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.bettor.to_account_info(),
-                to: ctx.accounts.pool_account.to_account_info(),
-            },
-        );
-        system_program::transfer(cpi_context, amount)?;
+        // Move the stake from the bettor into the pool (or its vault)
+        pool.deposit(
+            &ctx.accounts.bettor.to_account_info(),
+            ctx.accounts.bettor_tokens.as_ref(),
+            &ctx.accounts.system_program,
+            amount,
+        )?;
 
         // Update dare pools
-        dare.total_pool += amount;
+        dare.total_pool = payout::add(dare.total_pool, amount)?;
         match bet_type {
-            BetType::WillDo => dare.will_do_pool += amount,
-            BetType::WontDo => dare.wont_do_pool += amount,
+            BetType::WillDo => dare.will_do_pool = payout::add(dare.will_do_pool, amount)?,
+            BetType::WontDo => dare.wont_do_pool = payout::add(dare.wont_do_pool, amount)?,
+        }
+
+        // Record bet, initializing the position on first use
+        if bet.dare == Pubkey::default() {
+            bet.dare = dare.key();
+            bet.bettor = ctx.accounts.bettor.key();
+            bet.is_claimed = false;
+            bet.bump = ctx.bumps.bet;
         }
+        bet.deposit(bet_type.clone(), amount, now)?;
 
-        // Record bet
-        bet.dare = dare.key();
-        bet.bettor = ctx.accounts.bettor.key();
-        bet.amount = amount;
-        bet.bet_type = bet_type;
-        bet.bet_timestamp = Clock::get()?.unix_timestamp; // Store when bet was placed
-        bet.is_claimed = false;
-        bet.bump = ctx.bumps.bet;
+        emit_cpi!(BetPlaced {
+            dare: dare.key(),
+            bettor: bet.bettor,
+            bet_type,
+            amount,
+            position_will_do: bet.will_do_amount,
+            position_wont_do: bet.wont_do_amount,
+            will_do_pool: dare.will_do_pool,
+            wont_do_pool: dare.wont_do_pool,
+            total_pool: dare.total_pool,
+            timestamp: now,
+        });
 
         Ok(())
     }
@@ -91,17 +622,20 @@ pub mod dare_betting {
         let dare = &mut ctx.accounts.dare;
         let proof_submission = &mut ctx.accounts.proof_submission;
         
-        require!(Clock::get()?.unix_timestamp < dare.deadline, ErrorCode::DareExpired);
-        require!(!dare.is_completed, ErrorCode::DareAlreadyCompleted);
-        require!(!dare.is_expired, ErrorCode::DareExpired);
+        dare.require_not_paused(&ctx.accounts.config, PAUSE_PROOF_SUBMISSION)?;
+        dare.refresh_status(Clock::get()?.unix_timestamp)?;
+        dare.require_status(&[DareStatus::Open, DareStatus::Locked])?;
 
         // Initialize the proof submission account
         proof_submission.dare = dare.key();
         proof_submission.submitter = ctx.accounts.submitter.key();
+        proof_submission.submission_index = dare.submission_count;
         proof_submission.proof_hash = proof_hash;
         proof_submission.proof_description = proof_description;
         proof_submission.submission_timestamp = Clock::get()?.unix_timestamp;
         proof_submission.is_approved = false; // Requires admin approval
+        proof_submission.is_rejected = false;
+        proof_submission.rejection_reason = 0;
         proof_submission.approved_by = Pubkey::default();
         proof_submission.approval_timestamp = 0;
         proof_submission.likes_count = 0;
@@ -110,392 +644,1849 @@ pub mod dare_betting {
         proof_submission.reward_claimed = false;
         proof_submission.bump = ctx.bumps.proof_submission;
 
-        // Increment submission count
-        dare.submission_count = dare.submission_count.checked_add(1).unwrap();
+        // Increment submission count (also the next submission's PDA index)
+        dare.submission_count = dare
+            .submission_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MaxSubmissionsReached)?;
+
+        emit_cpi!(ProofSubmitted {
+            dare: dare.key(),
+            proof_submission: proof_submission.key(),
+            submitter: proof_submission.submitter,
+            submission_index: proof_submission.submission_index,
+            proof_hash: proof_submission.proof_hash.clone(),
+            proof_description: proof_submission.proof_description.clone(),
+            timestamp: proof_submission.submission_timestamp,
+        });
 
         Ok(())
     }
 
-    pub fn select_winners(
-        ctx: Context<SelectWinners>,
-        first_place: Pubkey,
-        second_place: Pubkey,
-        third_place: Pubkey,
-    ) -> Result<()> {
+    pub fn approve_proof(ctx: Context<ApproveProof>) -> Result<()> {
         let dare = &mut ctx.accounts.dare;
-        
-        // Only platform authority can select winners
+        let proof_submission = &mut ctx.accounts.proof_submission;
+
+        // Only platform authority can moderate submissions
         require!(
-            ctx.accounts.platform_authority.key() == dare.platform_authority,
+            ctx.accounts.platform_authority.key() == ctx.accounts.config.admin,
             ErrorCode::Unauthorized
         );
-
-        // Can only select winners after deadline
+        dare.require_status(&[
+            DareStatus::Open,
+            DareStatus::Locked,
+            DareStatus::AwaitingResolution,
+            DareStatus::Completed,
+        ])?;
+        require!(!dare.winners_selected(), ErrorCode::WinnersAlreadySelected);
         require!(
-            Clock::get()?.unix_timestamp >= dare.deadline,
-            ErrorCode::DareNotFinalized
+            !proof_submission.is_approved && !proof_submission.is_rejected,
+            ErrorCode::ProofAlreadyReviewed
         );
 
-        // Can't select winners twice
-        require!(!dare.winners_selected, ErrorCode::WinnersAlreadySelected);
+        proof_submission.is_approved = true;
+        proof_submission.approved_by = ctx.accounts.platform_authority.key();
+        proof_submission.approval_timestamp = Clock::get()?.unix_timestamp;
+        dare.approved_count = dare
+            .approved_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
 
-        // Update winners
-        dare.first_place_winner = Some(first_place);
-        dare.second_place_winner = Some(second_place);
-        dare.third_place_winner = Some(third_place);
-        dare.winners_selected = true;
-        dare.is_completed = true;
+        emit_cpi!(ProofApproved {
+            dare: dare.key(),
+            proof_submission: proof_submission.key(),
+            reviewer: proof_submission.approved_by,
+            approved_count: dare.approved_count,
+            timestamp: proof_submission.approval_timestamp,
+        });
 
         Ok(())
     }
 
-    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
+    pub fn reject_proof(ctx: Context<ApproveProof>, reason_code: u8) -> Result<()> {
         let dare = &mut ctx.accounts.dare;
-        let bet = &mut ctx.accounts.bet;
-        
-        require!(!bet.is_claimed, ErrorCode::AlreadyClaimed);
-        
-        let mut payout = 0u64;
-        let creator_fee = dare.total_pool * 2 / 100; // 2% creator fee
-        let available_pool = dare.total_pool - creator_fee;
-
-        // Check if dare is completed with selected winners
-        if dare.is_completed && dare.winners_selected {
-            // For completed dares with selected winners, "Will do" bettors win
-            if bet.bet_type == BetType::WillDo && dare.will_do_pool > 0 {
-                // Calculate remaining pool after winner rewards (30% + 15% + 5% = 50%)
-                let winner_rewards_total = available_pool * 50 / 100;
-                let remaining_pool = available_pool - winner_rewards_total;
-                payout = (bet.amount * remaining_pool) / dare.will_do_pool;
-            }
-        } else if Clock::get()?.unix_timestamp >= dare.deadline {
-            // Dare expired - "Won't do" bettors win
-            dare.is_expired = true;
-            if bet.bet_type == BetType::WontDo && dare.wont_do_pool > 0 {
-                payout = (bet.amount * available_pool) / dare.wont_do_pool;
-            }
-        } else {
-            return Err(ErrorCode::DareNotFinalized.into());
-        }
-
-        require!(payout > 0, ErrorCode::NoPayout);
-
-        // Transfer SOL winnings from pool to winner
-        let dare_key = dare.key();
-        let seeds = &[
-            b"pool",
-            dare_key.as_ref(),
-            &[ctx.bumps.pool_account],
-        ];
-        let signer = &[&seeds[..]];
+        let proof_submission = &mut ctx.accounts.proof_submission;
 
-        let cpi_context = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.pool_account.to_account_info(),
-                to: ctx.accounts.winner.to_account_info(),
-            },
-            signer,
+        // Only platform authority can moderate submissions
+        require!(
+            ctx.accounts.platform_authority.key() == ctx.accounts.config.admin,
+            ErrorCode::Unauthorized
+        );
+        dare.require_status(&[
+            DareStatus::Open,
+            DareStatus::Locked,
+            DareStatus::AwaitingResolution,
+            DareStatus::Completed,
+        ])?;
+        require!(!dare.winners_selected(), ErrorCode::WinnersAlreadySelected);
+        require!(
+            !proof_submission.is_approved && !proof_submission.is_rejected,
+            ErrorCode::ProofAlreadyReviewed
         );
-        system_program::transfer(cpi_context, payout)?;
+        // 0 is reserved for "not rejected"
+        require!(reason_code != 0, ErrorCode::InvalidRejectionReason);
 
-        bet.is_claimed = true;
+        proof_submission.is_rejected = true;
+        proof_submission.rejection_reason = reason_code;
+        proof_submission.approved_by = ctx.accounts.platform_authority.key();
+        proof_submission.approval_timestamp = Clock::get()?.unix_timestamp;
+        dare.rejected_count = dare
+            .rejected_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit_cpi!(ProofRejected {
+            dare: dare.key(),
+            proof_submission: proof_submission.key(),
+            reviewer: proof_submission.approved_by,
+            reason_code,
+            rejected_count: dare.rejected_count,
+            timestamp: proof_submission.approval_timestamp,
+        });
 
         Ok(())
     }
 
-    pub fn claim_winner_reward(ctx: Context<ClaimWinnerReward>) -> Result<()> {
+    /// The admin ranks a completed `RankingMode::Admin` dare. With fewer than
+    /// three approved submissions `second_place` and `third_place` are left
+    /// out in turn, and the unfilled places' rewards are left for `sweep_dust`.
+    pub fn select_winners(ctx: Context<SelectWinners>) -> Result<()> {
         let dare = &mut ctx.accounts.dare;
-        let proof_submission = &mut ctx.accounts.proof_submission;
         
-        require!(dare.is_completed, ErrorCode::DareNotCompleted);
-        require!(dare.winners_selected, ErrorCode::DareNotFinalized);
-        require!(!proof_submission.reward_claimed, ErrorCode::AlreadyClaimed);
-        
-        // Verify this submission is a winner
-        require!(proof_submission.is_winner, ErrorCode::NotSubmitter);
+        // Only platform authority can select winners
         require!(
-            proof_submission.submitter == ctx.accounts.winner.key(),
-            ErrorCode::NotSubmitter
+            ctx.accounts.platform_authority.key() == ctx.accounts.config.admin,
+            ErrorCode::Unauthorized
         );
 
-        let creator_fee = dare.total_pool * 2 / 100;
-        let available_pool = dare.total_pool - creator_fee;
-        
-        // Calculate reward based on winner rank
-        let reward_percentage = match proof_submission.winner_rank {
-            1 => 30, // 1st place: 30%
-            2 => 15, // 2nd place: 15%
-            3 => 5,  // 3rd place: 5%
-            _ => return Err(ErrorCode::InvalidWinnerRank.into()),
-        };
-        
-        let winner_reward = available_pool * reward_percentage / 100;
+        // Winners are ranked once the dare has been resolved as completed
+        dare.require_status(&[DareStatus::Completed])?;
+        require!(dare.ranking_mode == RankingMode::Admin, ErrorCode::RankedByLikes);
+        require!(!dare.winners_selected(), ErrorCode::WinnersAlreadySelected);
 
-        // Transfer SOL reward from pool to winner
-        let dare_key = dare.key();
-        let seeds = &[
-            b"pool",
-            dare_key.as_ref(),
-            &[ctx.bumps.pool_account],
+        let winners = [
+            Some(ctx.accounts.first_place.key()),
+            ctx.accounts.second_place.as_ref().map(|s| s.key()),
+            ctx.accounts.third_place.as_ref().map(|s| s.key()),
         ];
-        let signer = &[&seeds[..]];
 
-        let cpi_context = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.pool_account.to_account_info(),
-                to: ctx.accounts.winner.to_account_info(),
-            },
-            signer,
+        // Places are filled in order, one per approved submission up to three
+        let filled = winners.iter().take_while(|w| w.is_some()).count();
+        require!(
+            winners[filled..].iter().all(Option::is_none)
+                && filled == dare.approved_count.min(3) as usize,
+            ErrorCode::UnfilledWinnerPlace
         );
-        system_program::transfer(cpi_context, winner_reward)?;
 
-        // Mark as claimed
-        proof_submission.reward_claimed = true;
-        
-        // Update dare claimed status
-        match proof_submission.winner_rank {
-            1 => dare.first_place_claimed = true,
-            2 => dare.second_place_claimed = true,
-            3 => dare.third_place_claimed = true,
-            _ => {},
+        // The same submission can't take more than one place
+        require!(
+            (0..filled).all(|i| !winners[i + 1..filled].contains(&winners[i])),
+            ErrorCode::DuplicateWinner
+        );
+
+        // Rank the winning submissions (ownership and approval are enforced by the account constraints)
+        let ranked = [
+            Some(&mut ctx.accounts.first_place),
+            ctx.accounts.second_place.as_mut(),
+            ctx.accounts.third_place.as_mut(),
+        ];
+        for (rank, proof_submission) in (1u8..).zip(ranked.into_iter().flatten()) {
+            proof_submission.is_winner = true;
+            proof_submission.winner_rank = rank;
         }
 
+        // Update winners
+        [dare.first_place_winner, dare.second_place_winner, dare.third_place_winner] = winners;
+
+        emit_cpi!(WinnersSelected {
+            dare: dare.key(),
+            first_place: dare.first_place_winner,
+            second_place: dare.second_place_winner,
+            third_place: dare.third_place_winner,
+        });
+
         Ok(())
     }
 
-    pub fn cash_out_early(ctx: Context<CashOutEarly>) -> Result<()> {
+    /// Permissionless ranking for dares created with `RankingMode::Likes`, in
+    /// place of `select_winners`: once the dare is resolved as completed, ranks
+    /// the top three approved submissions by likes (see `ranking::compare`).
+    /// Every submission must have been reviewed, and the remaining accounts must
+    /// be exactly the dare's approved submissions, in any order. With fewer than
+    /// three approved submissions the unfilled places stay empty and their
+    /// rewards are left for `sweep_dust`. All approved submissions must be
+    /// passed in one transaction.
+    pub fn finalize_rankings<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeRankings<'info>>,
+    ) -> Result<()> {
         let dare = &mut ctx.accounts.dare;
-        let bet = &mut ctx.accounts.bet;
-        let current_time = Clock::get()?.unix_timestamp;
-        
-        require!(!bet.is_claimed, ErrorCode::AlreadyClaimed);
-        require!(!dare.is_completed, ErrorCode::DareAlreadyCompleted);
-        require!(current_time < dare.deadline, ErrorCode::DareExpired);
-        
-        // Check if within 10 minutes (600 seconds) of placing the bet
-        let ten_minutes = 600;
-        let time_since_bet = current_time - bet.bet_timestamp;
+        let dare_key = dare.key();
+
+        dare.require_status(&[DareStatus::Completed])?;
+        require!(dare.ranking_mode == RankingMode::Likes, ErrorCode::RankedByAdmin);
+        require!(!dare.winners_selected(), ErrorCode::WinnersAlreadySelected);
         require!(
-            time_since_bet <= ten_minutes,
-            ErrorCode::CashOutTooLate
+            dare.approved_count.checked_add(dare.rejected_count) == Some(dare.submission_count),
+            ErrorCode::ProofsPendingReview
+        );
+        require!(dare.approved_count > 0, ErrorCode::NoProofSubmitted);
+        require!(
+            ctx.remaining_accounts.len() == dare.approved_count as usize,
+            ErrorCode::IncompleteSubmissionSet
         );
 
-        // Calculate cash out amount with 10% penalty
-        let penalty = bet.amount * 10 / 100; // 10% penalty
-        let cash_out_amount = bet.amount - penalty;
-
-        // Update dare pools by removing the bet amount
-        match bet.bet_type {
-            BetType::WillDo => dare.will_do_pool -= bet.amount,
-            BetType::WontDo => dare.wont_do_pool -= bet.amount,
+        // Each account must be a distinct approved submission of this dare; with
+        // the count matching `approved_count` that makes the set complete
+        let mut submissions: Vec<Account<'info, ProofSubmission>> =
+            Vec::with_capacity(ctx.remaining_accounts.len());
+        for info in ctx.remaining_accounts {
+            let submission = Account::<ProofSubmission>::try_from(info)?;
+            require!(submission.dare == dare_key, ErrorCode::SubmissionDareMismatch);
+            require!(submission.is_approved, ErrorCode::ProofNotApproved);
+            require!(
+                submissions.iter().all(|s| s.key() != submission.key()),
+                ErrorCode::IncompleteSubmissionSet
+            );
+            submissions.push(submission);
         }
-        dare.total_pool -= bet.amount;
 
-        // Transfer SOL cash out amount to bettor
-        let dare_key = dare.key();
-        let seeds = &[
-            b"pool",
-            dare_key.as_ref(),
-            &[ctx.bumps.pool_account],
-        ];
-        let signer = &[&seeds[..]];
+        submissions.sort_by(|a, b| ranking::compare(a, b));
 
-        let cpi_context = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.pool_account.to_account_info(),
-                to: ctx.accounts.bettor.to_account_info(),
-            },
-            signer,
-        );
-        system_program::transfer(cpi_context, cash_out_amount)?;
+        let mut winners = [None; 3];
+        for ((rank, winner), submission) in (1u8..).zip(&mut winners).zip(&mut submissions) {
+            submission.is_winner = true;
+            submission.winner_rank = rank;
+            submission.exit(&crate::ID)?;
+            *winner = Some(submission.key());
+        }
+        [dare.first_place_winner, dare.second_place_winner, dare.third_place_winner] = winners;
 
-        // Mark bet as claimed to prevent double claiming
-        bet.is_claimed = true;
+        emit_cpi!(WinnersSelected {
+            dare: dare_key,
+            first_place: dare.first_place_winner,
+            second_place: dare.second_place_winner,
+            third_place: dare.third_place_winner,
+        });
 
         Ok(())
     }
 
-    pub fn claim_creator_fee(ctx: Context<ClaimCreatorFee>) -> Result<()> {
+    /// Freezes the outcome once the proof deadline has passed. Until
+    /// `resolution_deadline` only the admin can resolve, choosing either side;
+    /// after it anyone can, and the dare resolves as failed ("won't do" wins).
+    /// An oracle dare's oracle account must be passed then, and whatever
+    /// outcome it reports when this is called wins over the fallback, even one
+    /// reported after the deadline (as with `resolve_from_oracle`). A winning side
+    /// nobody bet on puts the dare into refund mode instead.
+    /// Claims open once the dispute window that starts here has passed.
+    pub fn resolve_dare(ctx: Context<ResolveDare>, outcome: BetType) -> Result<()> {
         let dare = &mut ctx.accounts.dare;
-        
-        require!(!dare.creator_fee_claimed, ErrorCode::CreatorFeeAlreadyClaimed);
-        require!(
-            dare.is_completed || Clock::get()?.unix_timestamp >= dare.deadline,
-            ErrorCode::DareNotFinalized
-        );
+        let now = Clock::get()?.unix_timestamp;
 
-        let creator_fee = dare.total_pool * 2 / 100;
-
-        // Transfer SOL creator fee from pool to creator
-        let dare_key = dare.key();
-        let seeds = &[
-            b"pool",
-            dare_key.as_ref(),
-            &[ctx.bumps.pool_account],
-        ];
-        let signer = &[&seeds[..]];
+        dare.refresh_status(now)?;
+        dare.require_status(&[DareStatus::AwaitingResolution])?;
 
-        let cpi_context = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.pool_account.to_account_info(),
-                to: ctx.accounts.creator.to_account_info(),
-            },
-            signer,
-        );
-        system_program::transfer(cpi_context, creator_fee)?;
+        let past_deadline = now >= dare.resolution_deadline;
+        let outcome = match dare.resolution_mode {
+            // A community vote decides the outcome even when nobody votes
+            ResolutionMode::Community => return err!(ErrorCode::ResolvedByCommunity),
+            ResolutionMode::Oracle { oracle, program } if past_deadline => {
+                let oracle_account = ctx
+                    .accounts
+                    .oracle
+                    .as_ref()
+                    .ok_or(ErrorCode::InvalidOracleAccount)?;
+                require!(oracle_account.key() == oracle, ErrorCode::InvalidOracleAccount);
+                // An account the oracle program never wrote to hasn't reported
+                if *oracle_account.owner == program {
+                    oracle::OracleOutcome::try_from_account(oracle_account)?
+                        .outcome()
+                        .unwrap_or(BetType::WontDo)
+                } else {
+                    BetType::WontDo
+                }
+            }
+            _ if past_deadline => BetType::WontDo,
+            // Committee and oracle dares are decided by their votes or data feed
+            ResolutionMode::Committee { .. } => return err!(ErrorCode::ResolvedByCommittee),
+            ResolutionMode::Oracle { .. } => return err!(ErrorCode::ResolvedByOracle),
+            ResolutionMode::Admin => {
+                require!(
+                    ctx.accounts.resolver.key() == ctx.accounts.config.admin,
+                    ErrorCode::Unauthorized
+                );
+                outcome
+            }
+        };
 
-        dare.creator_fee_claimed = true;
+        dare.resolve(outcome, now, ctx.accounts.config.disputes.window_secs)?;
 
-        Ok(())
-    }
+        emit_cpi!(DareResolved::new(dare));
 
-    pub fn like_proof_submission(ctx: Context<LikeProofSubmission>) -> Result<()> {
-        let proof_submission = &mut ctx.accounts.proof_submission;
-        
-        // Increment likes count
-        proof_submission.likes_count += 1;
-        
         Ok(())
     }
 
-    /// Emergency withdrawal function - ONLY for developer in critical situations
-    /// This allows the developer to withdraw all funds from a dare's pool
-    /// Use cases: Contract bug, stuck funds, security issue
-    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+    /// Permissionless: resolves an oracle dare from the outcome its oracle
+    /// account reports. Can be called any time after the proof deadline once
+    /// the oracle has reported, until the dare is resolved some other way.
+    pub fn resolve_from_oracle(ctx: Context<ResolveFromOracle>) -> Result<()> {
         let dare = &mut ctx.accounts.dare;
-        
-        // CRITICAL: Only the hardcoded developer address can call this
-        const DEVELOPER_PUBKEY: &str = "9DvhKAT7bn5n7YqRTTAgvgnmtxPro1qiTaHkz4vzn1cK";
-        let developer_key = Pubkey::from_str(DEVELOPER_PUBKEY)
-            .map_err(|_| ErrorCode::InvalidDeveloperKey)?;
-        
+        let oracle_account = &ctx.accounts.oracle;
+        let now = Clock::get()?.unix_timestamp;
+
+        let ResolutionMode::Oracle { oracle, program } = dare.resolution_mode else {
+            return err!(ErrorCode::NotOracleResolved);
+        };
         require!(
-            ctx.accounts.developer.key() == developer_key,
-            ErrorCode::UnauthorizedEmergencyWithdrawal
+            oracle_account.key() == oracle && *oracle_account.owner == program,
+            ErrorCode::InvalidOracleAccount
         );
+        dare.refresh_status(now)?;
+        dare.require_status(&[DareStatus::AwaitingResolution])?;
 
-        // Get total pool balance
-        let pool_balance = ctx.accounts.pool_account.lamports();
-        
-        require!(pool_balance > 0, ErrorCode::EmptyPool);
+        let outcome = oracle::OracleOutcome::try_from_account(oracle_account)?
+            .outcome()
+            .ok_or(ErrorCode::OracleNotResolved)?;
 
-        // Transfer all SOL from pool to developer
-        let dare_key = dare.key();
-        let seeds = &[
-            b"pool",
-            dare_key.as_ref(),
-            &[ctx.bumps.pool_account],
-        ];
-        let signer = &[&seeds[..]];
+        dare.resolve(outcome, now, ctx.accounts.config.disputes.window_secs)?;
+
+        emit_cpi!(DareResolved::new(dare));
+
+        Ok(())
+    }
+
+    /// A bettor's vote on a community-resolved dare, weighted by their total
+    /// stake. Voting runs from the proof deadline until the resolution
+    /// deadline, and each bettor votes once.
+    pub fn cast_vote(ctx: Context<CastVote>, outcome: BetType) -> Result<()> {
+        let dare = &mut ctx.accounts.dare;
+        let now = Clock::get()?.unix_timestamp;
+        let weight = ctx.accounts.bet.total_amount()?;
+
+        require!(
+            dare.resolution_mode == ResolutionMode::Community,
+            ErrorCode::NotCommunityResolved
+        );
+        require!(weight > 0, ErrorCode::NotABettor);
+        dare.refresh_status(now)?;
+        dare.require_status(&[DareStatus::AwaitingResolution])?;
+        require!(now < dare.resolution_deadline, ErrorCode::VotingClosed);
+
+        let vote = &mut ctx.accounts.vote;
+        vote.dare = dare.key();
+        vote.voter = ctx.accounts.voter.key();
+        vote.outcome = outcome.clone();
+        vote.weight = weight;
+        vote.voted_at = now;
+        vote.bump = ctx.bumps.vote;
+
+        match outcome {
+            BetType::WillDo => {
+                dare.community_will_do_weight = payout::add(dare.community_will_do_weight, weight)?
+            }
+            BetType::WontDo => {
+                dare.community_wont_do_weight = payout::add(dare.community_wont_do_weight, weight)?
+            }
+        }
+
+        emit_cpi!(VoteCast {
+            dare: dare.key(),
+            voter: vote.voter,
+            outcome: vote.outcome.clone(),
+            weight,
+            will_do_weight: dare.community_will_do_weight,
+            wont_do_weight: dare.community_wont_do_weight,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: resolves a community-voted dare once voting has closed.
+    /// "Will do" needs a strict majority of the voted stake; a tie (or no
+    /// votes) resolves as failed.
+    pub fn finalize_community_vote(ctx: Context<FinalizeCommunityVote>) -> Result<()> {
+        let dare = &mut ctx.accounts.dare;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            dare.resolution_mode == ResolutionMode::Community,
+            ErrorCode::NotCommunityResolved
+        );
+        dare.refresh_status(now)?;
+        dare.require_status(&[DareStatus::AwaitingResolution])?;
+        require!(now >= dare.resolution_deadline, ErrorCode::VotingStillOpen);
+
+        let outcome = if dare.community_will_do_weight > dare.community_wont_do_weight {
+            BetType::WillDo
+        } else {
+            BetType::WontDo
+        };
+
+        dare.resolve(outcome, now, ctx.accounts.config.disputes.window_secs)?;
+
+        emit_cpi!(DareResolved::new(dare));
+
+        Ok(())
+    }
+
+    /// A committee member's vote on a committee-resolved dare, recorded in its
+    /// own account for auditability. The dare resolves as soon as one outcome
+    /// has `threshold` votes. Members vote once, during the resolution window.
+    pub fn vote_resolution(ctx: Context<VoteResolution>, outcome: BetType) -> Result<()> {
+        let dare = &mut ctx.accounts.dare;
+        let committee = &ctx.accounts.committee;
+        let member = ctx.accounts.member.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            dare.resolution_mode == ResolutionMode::Committee { committee: committee.key() },
+            ErrorCode::CommitteeMismatch
+        );
+        require!(committee.members.contains(&member), ErrorCode::NotCommitteeMember);
+        dare.refresh_status(now)?;
+        dare.require_status(&[DareStatus::AwaitingResolution])?;
+        require!(now < dare.resolution_deadline, ErrorCode::VotingClosed);
+
+        let vote = &mut ctx.accounts.vote;
+        vote.dare = dare.key();
+        vote.member = member;
+        vote.outcome = outcome.clone();
+        vote.voted_at = now;
+        vote.bump = ctx.bumps.vote;
+
+        let votes = match outcome {
+            BetType::WillDo => &mut dare.committee_will_do_votes,
+            BetType::WontDo => &mut dare.committee_wont_do_votes,
+        };
+        *votes = votes.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+        let resolved = *votes >= committee.threshold;
+
+        emit_cpi!(CommitteeVoteCast {
+            dare: dare.key(),
+            committee: committee.key(),
+            member,
+            outcome: outcome.clone(),
+            will_do_votes: dare.committee_will_do_votes,
+            wont_do_votes: dare.committee_wont_do_votes,
+        });
+
+        if resolved {
+            dare.resolve(outcome, now, ctx.accounts.config.disputes.window_secs)?;
+            emit_cpi!(DareResolved::new(dare));
+        }
+
+        Ok(())
+    }
+
+    /// Challenges the outcome during the dispute window. Any bettor on the dare
+    /// can open one by posting the configured bond, which is held in the
+    /// dispute account. Claims stay frozen until the arbiter rules, or until
+    /// `expire_dispute` once the ruling window has passed without a ruling.
+    pub fn open_dispute(ctx: Context<OpenDispute>) -> Result<()> {
+        let dare = &mut ctx.accounts.dare;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(ctx.accounts.bet.total_amount()? > 0, ErrorCode::NotABettor);
+        require!(dare.outcome.is_some(), ErrorCode::InvalidDareStatus);
+        require!(now < dare.dispute_deadline, ErrorCode::DisputeWindowClosed);
+        dare.transition(DareStatus::Disputed)?;
 
-        let cpi_context = CpiContext::new_with_signer(
+        let bond = ctx.accounts.config.disputes.bond;
+        let cpi_context = CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             system_program::Transfer {
-                from: ctx.accounts.pool_account.to_account_info(),
-                to: ctx.accounts.developer.to_account_info(),
+                from: ctx.accounts.challenger.to_account_info(),
+                to: ctx.accounts.dispute.to_account_info(),
             },
-            signer,
         );
-        system_program::transfer(cpi_context, pool_balance)?;
+        system_program::transfer(cpi_context, bond)?;
+
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.dare = dare.key();
+        dispute.challenger = ctx.accounts.challenger.key();
+        dispute.bond = bond;
+        dispute.opened_at = now;
+        dispute.ruling_deadline = now
+            .checked_add(ctx.accounts.config.disputes.ruling_window_secs)
+            .ok_or(ErrorCode::MathOverflow)?;
+        dispute.bump = ctx.bumps.dispute;
+
+        emit_cpi!(DisputeOpened {
+            dare: dare.key(),
+            challenger: dispute.challenger,
+            bond,
+            ruling_deadline: dispute.ruling_deadline,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// The config arbiter rules on an open dispute before its ruling deadline. Overturning flips the
+    /// outcome and pays the challenger their bond plus the configured reward,
+    /// as far as the dispute reserve covers it; otherwise the bond is slashed
+    /// to the treasury and added to the reserve. Rewards are only ever funded
+    /// by slashed bonds, never by protocol revenue. Either way claims open
+    /// right away and the dispute account is closed, returning its rent to the
+    /// challenger.
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, overturn: bool) -> Result<()> {
+        let dare = &mut ctx.accounts.dare;
+        let now = Clock::get()?.unix_timestamp;
+
+        dare.require_status(&[DareStatus::Disputed])?;
+        require!(now < ctx.accounts.dispute.ruling_deadline, ErrorCode::RulingWindowClosed);
+
+        let outcome = dare.outcome.clone().ok_or(ErrorCode::InvalidDareStatus)?;
+        let outcome = match (overturn, outcome) {
+            (false, outcome) => outcome,
+            (true, BetType::WillDo) => BetType::WontDo,
+            (true, BetType::WontDo) => BetType::WillDo,
+        };
+        dare.apply_outcome(outcome)?;
+        dare.settled_at = now;
+        dare.dispute_deadline = now;
+
+        let bond = ctx.accounts.dispute.bond;
+        let treasury = ctx.accounts.treasury.to_account_info();
+        let config = &mut ctx.accounts.config;
+        let (reward, bond_slashed) = if overturn {
+            let reward = config.disputes.reward.min(config.dispute_reserve);
+            config.dispute_reserve = payout::sub(config.dispute_reserve, reward)?;
+            payout::transfer_from_pool(&treasury, &ctx.accounts.challenger, reward)?;
+            (reward, 0)
+        } else {
+            payout::transfer_from_pool(&ctx.accounts.dispute.to_account_info(), &treasury, bond)?;
+            config.dispute_reserve = payout::add(config.dispute_reserve, bond)?;
+            (0, bond)
+        };
+
+        // Whatever is left (the bond if it stood, plus rent) goes back to the challenger
+        ctx.accounts.dispute.close(ctx.accounts.challenger.to_account_info())?;
+
+        emit_cpi!(DisputeResolved {
+            dare: dare.key(),
+            challenger: ctx.accounts.challenger.key(),
+            overturned: overturn,
+            outcome: dare.outcome.clone(),
+            status: dare.status,
+            reward,
+            bond_slashed,
+            dispute_reserve: ctx.accounts.config.dispute_reserve,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: closes a dispute the arbiter never ruled on. Once the
+    /// ruling deadline has passed the dare settles on its original outcome and
+    /// claims open right away. A dispute on a dare halted by `emergency_refund`
+    /// can be closed at any time. Either way the challenger gets their bond back.
+    pub fn expire_dispute(ctx: Context<ExpireDispute>) -> Result<()> {
+        let dare = &mut ctx.accounts.dare;
+        let now = Clock::get()?.unix_timestamp;
+
+        match dare.status {
+            DareStatus::Disputed => {
+                require!(
+                    now >= ctx.accounts.dispute.ruling_deadline,
+                    ErrorCode::RulingWindowOpen
+                );
+                let outcome = dare.outcome.clone().ok_or(ErrorCode::InvalidDareStatus)?;
+                dare.apply_outcome(outcome)?;
+                dare.settled_at = now;
+                dare.dispute_deadline = now;
+            }
+            DareStatus::EmergencyRefunding => {}
+            _ => return err!(ErrorCode::InvalidDareStatus),
+        }
+
+        let bond = ctx.accounts.dispute.bond;
+
+        // The bond and the rent go back to the challenger
+        ctx.accounts.dispute.close(ctx.accounts.challenger.to_account_info())?;
+
+        emit_cpi!(DisputeExpired {
+            dare: dare.key(),
+            challenger: ctx.accounts.challenger.key(),
+            outcome: dare.outcome.clone(),
+            status: dare.status,
+            bond_returned: bond,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
+        let pool = Pool::new(
+            &ctx.accounts.dare,
+            &ctx.accounts.pool_account,
+            ctx.bumps.pool_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+        )?;
+        let dare = &mut ctx.accounts.dare;
+        let bet = &mut ctx.accounts.bet;
+        
+        dare.require_not_paused(&ctx.accounts.config, PAUSE_CLAIMING)?;
+        require!(!bet.is_claimed, ErrorCode::AlreadyClaimed);
+        dare.require_status(&[DareStatus::Completed, DareStatus::Failed])?;
+        dare.require_claims_open(Clock::get()?.unix_timestamp)?;
+        
+        let winnings;
+        let winning_stake;
+
+        // Only the stake on the winning side of the position pays out
+        match dare.winning_side() {
+            Some(BetType::WillDo) => {
+                // For completed dares, "Will do" bettors win
+                winnings = payout::will_do_payout(
+                    bet.will_do_amount,
+                    dare.will_do_pool,
+                    dare.total_pool,
+                    &dare.fees,
+                )?;
+                winning_stake = bet.will_do_amount;
+            }
+            Some(BetType::WontDo) => {
+                // Dare failed - "Won't do" bettors win
+                winnings = payout::wont_do_payout(
+                    bet.wont_do_amount,
+                    dare.wont_do_pool,
+                    dare.total_pool,
+                    &dare.fees,
+                )?;
+                winning_stake = bet.wont_do_amount;
+            }
+            None => return Err(ErrorCode::DareNotFinalized.into()),
+        }
+
+        require!(winnings > 0, ErrorCode::NoPayout);
+
+        // Transfer winnings from pool to winner
+        pool.pay(
+            &ctx.accounts.winner.to_account_info(),
+            ctx.accounts.winner_tokens.as_ref(),
+            winnings,
+        )?;
+
+        bet.is_claimed = true;
+        dare.total_claimed = payout::add(dare.total_claimed, winnings)?;
+        dare.winning_stake_claimed = payout::add(dare.winning_stake_claimed, winning_stake)?;
+
+        pool.check_invariant(dare)?;
+
+        emit_cpi!(WinningsClaimed {
+            dare: dare.key(),
+            winner: ctx.accounts.winner.key(),
+            amount: winnings,
+            winning_stake,
+            total_claimed: dare.total_claimed,
+        });
+
+        Ok(())
+    }
+
+    pub fn claim_winner_reward(ctx: Context<ClaimWinnerReward>) -> Result<()> {
+        let pool = Pool::new(
+            &ctx.accounts.dare,
+            &ctx.accounts.pool_account,
+            ctx.bumps.pool_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+        )?;
+        let dare = &mut ctx.accounts.dare;
+        let proof_submission = &mut ctx.accounts.proof_submission;
+        
+        dare.require_not_paused(&ctx.accounts.config, PAUSE_CLAIMING)?;
+        dare.require_status(&[DareStatus::Completed])?;
+        dare.require_claims_open(Clock::get()?.unix_timestamp)?;
+        require!(!proof_submission.reward_claimed, ErrorCode::AlreadyClaimed);
+        
+        // Verify this submission is a winner
+        require!(proof_submission.is_winner, ErrorCode::NotSubmitter);
+        require!(
+            proof_submission.submitter == ctx.accounts.winner.key(),
+            ErrorCode::NotSubmitter
+        );
+
+        // Calculate reward based on winner rank
+        let winner_reward = payout::winner_reward(
+            dare.total_pool,
+            &dare.fees,
+            proof_submission.winner_rank,
+        )?;
+
+        // Transfer reward from pool to winner
+        pool.pay(
+            &ctx.accounts.winner.to_account_info(),
+            ctx.accounts.winner_tokens.as_ref(),
+            winner_reward,
+        )?;
+
+        // Mark as claimed
+        proof_submission.reward_claimed = true;
+        
+        // Update dare claimed status
+        match proof_submission.winner_rank {
+            1 => dare.first_place_claimed = true,
+            2 => dare.second_place_claimed = true,
+            3 => dare.third_place_claimed = true,
+            _ => {},
+        }
+        dare.total_claimed = payout::add(dare.total_claimed, winner_reward)?;
+
+        pool.check_invariant(dare)?;
+
+        emit_cpi!(WinnerRewardClaimed {
+            dare: dare.key(),
+            proof_submission: proof_submission.key(),
+            winner: ctx.accounts.winner.key(),
+            rank: proof_submission.winner_rank,
+            amount: winner_reward,
+            total_claimed: dare.total_claimed,
+        });
+
+        Ok(())
+    }
+
+    pub fn cash_out_early(ctx: Context<CashOutEarly>) -> Result<()> {
+        let pool = Pool::new(
+            &ctx.accounts.dare,
+            &ctx.accounts.pool_account,
+            ctx.bumps.pool_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+        )?;
+        let dare = &mut ctx.accounts.dare;
+        let bet = &mut ctx.accounts.bet;
+        let current_time = Clock::get()?.unix_timestamp;
+        let stake = bet.total_amount()?;
+        
+        dare.require_not_paused(&ctx.accounts.config, PAUSE_CASH_OUT)?;
+        require!(!bet.is_claimed, ErrorCode::AlreadyClaimed);
+        require!(stake > 0, ErrorCode::NoPayout);
+        dare.refresh_status(current_time)?;
+        dare.require_status(&[DareStatus::Open])?;
+        
+        // Only within 10 minutes of the position's first deposit
+        require!(bet.can_cash_out(current_time), ErrorCode::CashOutTooLate);
+
+        // The whole position (both sides) is cashed out; split the penalty between pool and treasury
+        let (cash_out_amount, penalty_to_pool, penalty_to_treasury) =
+            payout::cash_out(stake, &dare.fees)?;
+
+        // Update dare pools by removing the position. The pool's share of the
+        // penalty stays in total_pool and is paid out to the winning side.
+        dare.will_do_pool = payout::sub(dare.will_do_pool, bet.will_do_amount)?;
+        dare.wont_do_pool = payout::sub(dare.wont_do_pool, bet.wont_do_amount)?;
+        dare.total_pool = payout::sub(dare.total_pool, payout::sub(stake, penalty_to_pool)?)?;
+
+        // Transfer cash out amount to bettor
+        pool.pay(
+            &ctx.accounts.bettor.to_account_info(),
+            ctx.accounts.bettor_tokens.as_ref(),
+            cash_out_amount,
+        )?;
+
+        // Transfer the treasury's share of the penalty
+        pool.pay(
+            &ctx.accounts.treasury,
+            ctx.accounts.treasury_tokens.as_ref(),
+            penalty_to_treasury,
+        )?;
+
+        // Close out the position; the bettor may open a new one while betting is open
+        bet.will_do_amount = 0;
+        bet.wont_do_amount = 0;
+
+        pool.check_invariant(dare)?;
+
+        emit_cpi!(CashedOut {
+            dare: dare.key(),
+            bettor: ctx.accounts.bettor.key(),
+            stake,
+            refund: cash_out_amount,
+            penalty_to_pool,
+            penalty_to_treasury,
+            will_do_pool: dare.will_do_pool,
+            wont_do_pool: dare.wont_do_pool,
+            total_pool: dare.total_pool,
+        });
+
+        Ok(())
+    }
+
+    pub fn claim_creator_fee(ctx: Context<ClaimCreatorFee>) -> Result<()> {
+        let pool = Pool::new(
+            &ctx.accounts.dare,
+            &ctx.accounts.pool_account,
+            ctx.bumps.pool_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+        )?;
+        let dare = &mut ctx.accounts.dare;
+        
+        dare.require_not_paused(&ctx.accounts.config, PAUSE_CLAIMING)?;
+        require!(!dare.creator_fee_claimed, ErrorCode::CreatorFeeAlreadyClaimed);
+        dare.require_status(&[DareStatus::Completed, DareStatus::Failed])?;
+        dare.require_claims_open(Clock::get()?.unix_timestamp)?;
+
+        let creator_fee = payout::creator_fee(dare.total_pool, &dare.fees)?;
+
+        // Transfer creator fee from pool to creator
+        pool.pay(
+            &ctx.accounts.creator.to_account_info(),
+            ctx.accounts.creator_tokens.as_ref(),
+            creator_fee,
+        )?;
+
+        dare.creator_fee_claimed = true;
+        dare.total_claimed = payout::add(dare.total_claimed, creator_fee)?;
+
+        pool.check_invariant(dare)?;
+
+        emit_cpi!(CreatorFeeClaimed {
+            dare: dare.key(),
+            creator: dare.creator,
+            amount: creator_fee,
+            total_claimed: dare.total_claimed,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: moves the dare's protocol fee into the treasury once it is settled
+    pub fn collect_protocol_fee(ctx: Context<CollectProtocolFee>) -> Result<()> {
+        let pool = Pool::new(
+            &ctx.accounts.dare,
+            &ctx.accounts.pool_account,
+            ctx.bumps.pool_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+        )?;
+        let dare = &mut ctx.accounts.dare;
+
+        dare.require_not_paused(&ctx.accounts.config, PAUSE_CLAIMING)?;
+        require!(!dare.protocol_fee_collected, ErrorCode::ProtocolFeeAlreadyCollected);
+        dare.require_status(&[DareStatus::Completed, DareStatus::Failed])?;
+        dare.require_claims_open(Clock::get()?.unix_timestamp)?;
+
+        let protocol_fee = payout::protocol_fee(dare.total_pool, &dare.fees)?;
+
+        pool.pay(
+            &ctx.accounts.treasury,
+            ctx.accounts.treasury_tokens.as_ref(),
+            protocol_fee,
+        )?;
+
+        dare.protocol_fee_collected = true;
+        dare.total_claimed = payout::add(dare.total_claimed, protocol_fee)?;
+
+        pool.check_invariant(dare)?;
+
+        emit_cpi!(ProtocolFeeCollected {
+            dare: dare.key(),
+            amount: protocol_fee,
+            total_claimed: dare.total_claimed,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraws a dare. While nobody has money in it (`total_pool == 0`) the
+    /// creator or the admin can cancel it, and if nothing has been attached to
+    /// it yet (no proofs, and before the proof deadline) this closes the dare
+    /// and its pool, returning the rent to the creator. Once there are bets only
+    /// the admin can cancel, which puts the dare into refund mode so bettors can
+    /// `claim_refund`. Cancelling is no longer possible once payouts have started.
+    pub fn cancel_dare(ctx: Context<CancelDare>) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let is_creator = authority == ctx.accounts.dare.creator;
+        let is_admin = authority == ctx.accounts.config.admin;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(is_creator || is_admin, ErrorCode::Unauthorized);
+        require!(
+            ctx.accounts.dare.total_claimed == 0
+                && ctx.accounts.dare.status != DareStatus::Closed,
+            ErrorCode::DareAlreadySettled
+        );
+
+        ctx.accounts.dare.refresh_status(now)?;
+        let is_empty = ctx.accounts.dare.total_pool == 0;
+
+        // Proofs, their likes and resolution votes live at addresses derived from
+        // the dare's, which the creator can reuse for a new dare with the same
+        // title: only a dare none of them can exist for yet is closed
+        if is_empty
+            && ctx.accounts.dare.submission_count == 0
+            && matches!(ctx.accounts.dare.status, DareStatus::Open | DareStatus::Locked)
+        {
+            // Nothing at stake: close the accounts and return their rent
+            let token_pool = Pool::new(
+                &ctx.accounts.dare,
+                &ctx.accounts.pool_account,
+                ctx.bumps.pool_account,
+                &ctx.accounts.mint,
+                &ctx.accounts.vault,
+                &ctx.accounts.token_program,
+            )?;
+            token_pool.close_vault(&ctx.accounts.creator)?;
+            let pool = ctx.accounts.pool_account.to_account_info();
+            payout::transfer_from_pool(&pool, &ctx.accounts.creator, pool.lamports())?;
+            ctx.accounts.dare.close(ctx.accounts.creator.to_account_info())?;
+            emit_cpi!(DareCancelled {
+                dare: ctx.accounts.dare.key(),
+                authority,
+                closed: true,
+                timestamp: now,
+            });
+            return Ok(());
+        }
+
+        require!(is_empty || is_admin, ErrorCode::CancelRequiresAdmin);
+
+        let dare = &mut ctx.accounts.dare;
+        dare.transition(DareStatus::Cancelled)?;
+        dare.settled_at = now;
+        // A cancellation can't be disputed: refunds open right away
+        dare.dispute_deadline = dare.settled_at;
+
+        emit_cpi!(DareCancelled {
+            dare: dare.key(),
+            authority,
+            closed: false,
+            timestamp: dare.settled_at,
+        });
+
+        Ok(())
+    }
+
+    /// Returns a bettor's whole position (both sides), minus the dare's refund fee
+    /// which goes to the treasury. Available once the dare is refunding: either
+    /// it settled with nobody on the winning side, or it was cancelled. After an
+    /// `emergency_refund` the bettor instead gets their pro-rata share of what
+    /// the pool held, with no fee.
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        let pool = Pool::new(
+            &ctx.accounts.dare,
+            &ctx.accounts.pool_account,
+            ctx.bumps.pool_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+        )?;
+        let dare = &mut ctx.accounts.dare;
+        let bet = &mut ctx.accounts.bet;
+        let stake = bet.total_amount()?;
+
+        dare.require_not_paused(&ctx.accounts.config, PAUSE_CLAIMING)?;
+        require!(!bet.is_claimed, ErrorCode::AlreadyClaimed);
+        require!(stake > 0, ErrorCode::NoPayout);
+        dare.require_status(&[
+            DareStatus::Refunding,
+            DareStatus::Cancelled,
+            DareStatus::EmergencyRefunding,
+        ])?;
+        dare.require_claims_open(Clock::get()?.unix_timestamp)?;
+
+        let (refund, refund_fee) = if dare.status == DareStatus::EmergencyRefunding {
+            let refund =
+                payout::emergency_refund(stake, dare.emergency_pool, dare.emergency_stake)?;
+            dare.emergency_pool = payout::sub(dare.emergency_pool, refund)?;
+            dare.emergency_stake = payout::sub(dare.emergency_stake, stake)?;
+            (refund, 0)
+        } else {
+            let refund_fee = payout::apply_bps(stake, dare.fees.refund_fee_bps)?;
+            (payout::sub(stake, refund_fee)?, refund_fee)
+        };
+
+        pool.pay(
+            &ctx.accounts.bettor.to_account_info(),
+            ctx.accounts.bettor_tokens.as_ref(),
+            refund,
+        )?;
+        if refund_fee > 0 {
+            pool.pay(
+                &ctx.accounts.treasury,
+                ctx.accounts.treasury_tokens.as_ref(),
+                refund_fee,
+            )?;
+        }
+
+        bet.is_claimed = true;
+        dare.refunded_stake = payout::add(dare.refunded_stake, stake)?;
+        dare.total_claimed = payout::add(dare.total_claimed, payout::add(refund, refund_fee)?)?;
+
+        pool.check_invariant(dare)?;
+
+        emit_cpi!(RefundClaimed {
+            dare: dare.key(),
+            bettor: ctx.accounts.bettor.key(),
+            stake,
+            refund,
+            fee: refund_fee,
+            refunded_stake: dare.refunded_stake,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: sends everything above the pool's rent-exempt minimum (or the
+    /// vault's whole balance) to the treasury once the dare is settled and either every claim has been paid
+    /// (only rounding dust is left) or the claim window has elapsed (unclaimed
+    /// payouts are forfeited; see `Dare::claim_window_ends`). Claims are closed afterwards.
+    pub fn sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+        let pool = Pool::new(
+            &ctx.accounts.dare,
+            &ctx.accounts.pool_account,
+            ctx.bumps.pool_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+        )?;
+        let dare = &mut ctx.accounts.dare;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Forfeiting unclaimed payouts while claims are paused would be unfair
+        dare.require_not_paused(&ctx.accounts.config, PAUSE_CLAIMING)?;
+        dare.require_status(&[
+            DareStatus::Completed,
+            DareStatus::Failed,
+            DareStatus::Refunding,
+            DareStatus::Cancelled,
+            DareStatus::EmergencyRefunding,
+        ])?;
+        dare.require_claims_open(now)?;
+
+        let forfeited_obligations = payout::unclaimed_obligations(dare)?;
+        if forfeited_obligations > 0 {
+            require!(
+                now >= dare.claim_window_ends(&ctx.accounts.config)?,
+                ErrorCode::ClaimsOutstanding
+            );
+        }
+
+        let sweep_amount = pool.available()?;
+        pool.pay(&ctx.accounts.treasury, ctx.accounts.treasury_tokens.as_ref(), sweep_amount)?;
+
+        dare.transition(DareStatus::Closed)?;
+
+        emit_cpi!(DustSwept {
+            dare: dare.key(),
+            amount: sweep_amount,
+            forfeited_obligations,
+        });
+
+        Ok(())
+    }
+
+    /// One like per wallet: the `Like` account can only be created once. Likes
+    /// are frozen at the proof deadline, so they can't move once winners may be
+    /// ranked by them.
+    pub fn like_proof_submission(ctx: Context<LikeProofSubmission>) -> Result<()> {
+        let dare = &mut ctx.accounts.dare;
+        let proof_submission = &mut ctx.accounts.proof_submission;
+        let like = &mut ctx.accounts.like;
+
+        dare.refresh_status(Clock::get()?.unix_timestamp)?;
+        dare.require_status(&[DareStatus::Open, DareStatus::Locked])?;
 
-        // Mark dare as emergency withdrawn (optional flag)
-        dare.is_expired = true; // Repurpose this flag to prevent further actions
+        like.proof_submission = proof_submission.key();
+        like.liker = ctx.accounts.liker.key();
+        like.liked_at = Clock::get()?.unix_timestamp;
+        like.bump = ctx.bumps.like;
 
-        msg!("🚨 EMERGENCY WITHDRAWAL: {} SOL withdrawn by developer", pool_balance);
+        // Increment likes count
+        proof_submission.likes_count = proof_submission
+            .likes_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        emit_cpi!(ProofLiked {
+            proof_submission: proof_submission.key(),
+            liker: like.liker,
+            likes_count: proof_submission.likes_count,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraws a like, closing the `Like` account and refunding its rent.
+    /// Frozen at the proof deadline, like `like_proof_submission`.
+    pub fn unlike_proof_submission(ctx: Context<UnlikeProofSubmission>) -> Result<()> {
+        let dare = &mut ctx.accounts.dare;
+        let proof_submission = &mut ctx.accounts.proof_submission;
+
+        dare.refresh_status(Clock::get()?.unix_timestamp)?;
+        dare.require_status(&[DareStatus::Open, DareStatus::Locked])?;
+
+        proof_submission.likes_count = proof_submission
+            .likes_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit_cpi!(ProofUnliked {
+            proof_submission: proof_submission.key(),
+            liker: ctx.accounts.liker.key(),
+            likes_count: proof_submission.likes_count,
+        });
+
+        Ok(())
+    }
+
+    /// Halts a dare and hands what its pool still holds back to the bettors,
+    /// pro-rata to the stakes they haven't been paid for yet. Nothing goes to the
+    /// admin: bettors claim their share through `claim_refund`, and only what is
+    /// left after the claim window reaches the treasury through `sweep_dust`.
+    pub fn emergency_refund(ctx: Context<EmergencyRefund>) -> Result<()> {
+        let pool = Pool::new(
+            &ctx.accounts.dare,
+            &ctx.accounts.pool_account,
+            ctx.bumps.pool_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+        )?;
+        let dare = &mut ctx.accounts.dare;
+        let now = Clock::get()?.unix_timestamp;
+
+        dare.transition(DareStatus::EmergencyRefunding)?;
+        // Every stake still in the pool: winning positions already paid out and
+        // refunded positions are settled
+        dare.emergency_stake = payout::sub(
+            payout::add(dare.will_do_pool, dare.wont_do_pool)?,
+            payout::add(dare.winning_stake_claimed, dare.refunded_stake)?,
+        )?;
+        dare.emergency_pool = pool.available()?;
+        dare.settled_at = now;
+        // Refunds open right away, like a cancellation
+        dare.dispute_deadline = now;
+
+        emit_cpi!(EmergencyRefundStarted {
+            dare: dare.key(),
+            admin: ctx.accounts.admin.key(),
+            pool: dare.emergency_pool,
+            outstanding_stake: dare.emergency_stake,
+            timestamp: now,
+        });
 
         Ok(())
     }
 }
 
+/// Whether replacing the pause bits `paused` with `flags` lifts a claims pause
+fn resumes_claims(paused: u8, flags: u8) -> bool {
+    paused & PAUSE_CLAIMING != 0 && flags & PAUSE_CLAIMING == 0
+}
+
+/// Records `kind` in a new `PendingAction`, executable once the config's
+/// withdrawal timelock has passed
+fn queue_action(ctx: Context<QueueAction>, action_id: u64, kind: PendingActionKind) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let pending_action = &mut ctx.accounts.pending_action;
+    pending_action.action_id = action_id;
+    pending_action.kind = kind;
+    pending_action.queued_at = now;
+    pending_action.executable_at = now
+        .checked_add(ctx.accounts.config.withdrawal_timelock_secs)
+        .ok_or(ErrorCode::MathOverflow)?;
+    pending_action.bump = ctx.bumps.pending_action;
+
+    emit_cpi!(ActionQueued {
+        pending_action: pending_action.key(),
+        action_id,
+        kind,
+        queued_at: now,
+        executable_at: pending_action.executable_at,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ProgramConfig::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 0,
+        seeds = [b"treasury"],
+        bump
+    )]
+    /// CHECK: This is a PDA that accumulates protocol revenue in SOL
+    pub treasury: AccountInfo<'info>,
+    
+    /// This program's program data account, which names its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(committee_id: u64)]
+pub struct CreateCommittee<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = ResolverCommittee::LEN,
+        seeds = [b"committee", committee_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub committee: Account<'info, ResolverCommittee>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetAllowedMint<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = AllowedMint::LEN,
+        seeds = [b"allowed_mint", mint.key().as_ref()],
+        bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveAllowedMint<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"allowed_mint", allowed_mint.mint.as_ref()],
+        bump = allowed_mint.bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Only for `PauseScope::Dare`: the dare to pause
+    #[account(mut)]
+    pub dare: Option<Account<'info, Dare>>,
+    
+    pub admin: Signer<'info>,
+}
+
+/// Shared by every instruction that queues a `PendingAction`
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(action_id: u64)]
+pub struct QueueAction<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = PendingAction::LEN,
+        seeds = [b"pending_action", config.key().as_ref(), action_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecutePendingAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin,
+        has_one = treasury
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            b"pending_action",
+            config.key().as_ref(),
+            pending_action.action_id.to_le_bytes().as_ref()
+        ],
+        bump = pending_action.bump
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    /// CHECK: This is a PDA that holds protocol revenue and owns the treasury's token accounts
+    pub treasury: AccountInfo<'info>,
+    
+    /// CHECK: Withdrawals only: must be the queued recipient, a wallet for SOL,
+    /// a token account for tokens
+    #[account(mut)]
+    pub recipient: Option<UncheckedAccount<'info>>,
+    
+    /// Token withdrawals only: the mint, the treasury's token account and the token program
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub treasury_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    /// CHECK: The config admin, receives the pending action's rent
+    #[account(mut)]
+    pub admin: AccountInfo<'info>,
+    
+    pub executor: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelPendingAction<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            b"pending_action",
+            config.key().as_ref(),
+            pending_action.action_id.to_le_bytes().as_ref()
+        ],
+        bump = pending_action.bump
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub new_admin: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(title: String, description: String)]
+pub struct CreateDare<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = Dare::LEN,
+        seeds = [b"dare", creator.key().as_ref(), title.as_bytes()],
+        bump
+    )]
+    pub dare: Account<'info, Dare>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 0,
+        seeds = [b"pool", dare.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a PDA that holds a SOL dare's stakes and owns a token dare's vault
+    pub pool_account: AccountInfo<'info>,
+    
+    /// Token dares only: the mint bets are placed in
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"vault", dare.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pool_account,
+        token::token_program = token_program
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Token dares only: the mint's allowlist entry
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+    
+    /// Committee dares only: the committee named by the resolution mode
+    pub committee: Option<Account<'info, ResolverCommittee>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlaceBet<'info> {
+    #[account(mut)]
+    pub dare: Account<'info, Dare>,
+    
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = Bet::LEN,
+        seeds = [b"bet", dare.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub bet: Account<'info, Bet>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool", dare.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a PDA that holds SOL
+    pub pool_account: AccountInfo<'info>,
+    
+    /// Token dares only: the dare's mint, vault and token program
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", dare.key().as_ref()],
+        bump
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    /// Token dares only: the bettor's token account for the dare's mint
+    #[account(mut)]
+    pub bettor_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Token dares only: the mint's allowlist entry
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SubmitProof<'info> {
+    #[account(mut)]
+    pub dare: Account<'info, Dare>,
+    
+    #[account(
+        init,
+        payer = submitter,
+        space = ProofSubmission::LEN,
+        seeds = [b"proof", dare.key().as_ref(), &dare.submission_count.to_le_bytes()],
+        bump
+    )]
+    pub proof_submission: Account<'info, ProofSubmission>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimWinnings<'info> {
+    #[account(mut)]
+    pub dare: Account<'info, Dare>,
+    
+    #[account(
+        mut,
+        seeds = [b"bet", dare.key().as_ref(), winner.key().as_ref()],
+        bump = bet.bump
+    )]
+    pub bet: Account<'info, Bet>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool", dare.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a PDA that holds SOL
+    pub pool_account: AccountInfo<'info>,
+    
+    /// Token dares only: the dare's mint, vault and token program
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", dare.key().as_ref()],
+        bump
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    /// Token dares only: the winner's token account for the dare's mint
+    #[account(mut)]
+    pub winner_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub winner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelDare<'info> {
+    #[account(
+        mut,
+        has_one = creator
+    )]
+    pub dare: Account<'info, Dare>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool", dare.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a PDA that holds SOL
+    pub pool_account: AccountInfo<'info>,
+    
+    /// Token dares only: the dare's mint, vault and token program
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", dare.key().as_ref()],
+        bump
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// CHECK: The dare's creator, receives the rent when the dare is closed
+    #[account(mut)]
+    pub creator: AccountInfo<'info>,
+    
+    /// The dare's creator or the config admin
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub dare: Account<'info, Dare>,
+    
+    #[account(
+        mut,
+        seeds = [b"bet", dare.key().as_ref(), bettor.key().as_ref()],
+        bump = bet.bump
+    )]
+    pub bet: Account<'info, Bet>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool", dare.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a PDA that holds SOL
+    pub pool_account: AccountInfo<'info>,
+    
+    /// Token dares only: the dare's mint, vault and token program
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", dare.key().as_ref()],
+        bump
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    /// Token dares only: the bettor's token account for the dare's mint
+    #[account(mut)]
+    pub bettor_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Token dares only: the treasury's token account for the dare's mint
+    #[account(mut)]
+    pub treasury_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = treasury
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    /// CHECK: The treasury PDA, checked against `config.treasury`
+    pub treasury: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimWinnerReward<'info> {
+    #[account(mut)]
+    pub dare: Account<'info, Dare>,
+    
+    #[account(
+        mut,
+        seeds = [b"proof", dare.key().as_ref(), &proof_submission.submission_index.to_le_bytes()],
+        bump = proof_submission.bump
+    )]
+    pub proof_submission: Account<'info, ProofSubmission>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool", dare.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a PDA that holds SOL
+    pub pool_account: AccountInfo<'info>,
+    
+    /// Token dares only: the dare's mint, vault and token program
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", dare.key().as_ref()],
+        bump
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    /// Token dares only: the winner's token account for the dare's mint
+    #[account(mut)]
+    pub winner_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub winner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(title: String, description: String)]
-pub struct CreateDare<'info> {
+pub struct LikeProofSubmission<'info> {
     #[account(
-        init,
-        payer = creator,
-        space = Dare::LEN,
-        seeds = [b"dare", creator.key().as_ref(), title.as_bytes()],
-        bump
+        mut,
+        seeds = [b"proof", dare.key().as_ref(), &proof_submission.submission_index.to_le_bytes()],
+        bump = proof_submission.bump
     )]
+    pub proof_submission: Account<'info, ProofSubmission>,
+    
+    #[account(mut)]
     pub dare: Account<'info, Dare>,
     
     #[account(
         init,
-        payer = creator,
-        space = 0,
-        seeds = [b"pool", dare.key().as_ref()],
+        payer = liker,
+        space = Like::LEN,
+        seeds = [b"like", proof_submission.key().as_ref(), liker.key().as_ref()],
         bump
     )]
-    /// CHECK: This is a PDA that will hold SOL
-    pub pool_account: AccountInfo<'info>,
+    pub like: Account<'info, Like>,
     
     #[account(mut)]
-    pub creator: Signer<'info>,
+    pub liker: Signer<'info>,
     
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct PlaceBet<'info> {
-    #[account(mut)]
-    pub dare: Account<'info, Dare>,
-    
+pub struct UnlikeProofSubmission<'info> {
     #[account(
-        init,
-        payer = bettor,
-        space = Bet::LEN,
-        seeds = [b"bet", dare.key().as_ref(), bettor.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"proof", dare.key().as_ref(), &proof_submission.submission_index.to_le_bytes()],
+        bump = proof_submission.bump
     )]
-    pub bet: Account<'info, Bet>,
+    pub proof_submission: Account<'info, ProofSubmission>,
+    
+    #[account(mut)]
+    pub dare: Account<'info, Dare>,
     
     #[account(
         mut,
-        seeds = [b"pool", dare.key().as_ref()],
-        bump
+        close = liker,
+        seeds = [b"like", proof_submission.key().as_ref(), liker.key().as_ref()],
+        bump = like.bump
     )]
-    /// CHECK: This is a PDA that holds SOL
-    pub pool_account: AccountInfo<'info>,
+    pub like: Account<'info, Like>,
     
     #[account(mut)]
-    pub bettor: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
+    pub liker: Signer<'info>,
 }
 
 #[derive(Accounts)]
-#[instruction(proof_hash: String)]
-pub struct SubmitProof<'info> {
+pub struct ClaimCompletionReward<'info> {
     #[account(mut)]
     pub dare: Account<'info, Dare>,
     
     #[account(
-        init,
-        payer = submitter,
-        space = ProofSubmission::LEN,
-        seeds = [b"proof", dare.key().as_ref(), submitter.key().as_ref(), proof_hash.as_bytes()],
+        mut,
+        seeds = [b"pool", dare.key().as_ref()],
         bump
     )]
-    pub proof_submission: Account<'info, ProofSubmission>,
+    /// CHECK: This is a PDA that holds SOL
+    pub pool_account: AccountInfo<'info>,
     
     #[account(mut)]
     pub submitter: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct ClaimWinnings<'info> {
+pub struct CashOutEarly<'info> {
     #[account(mut)]
     pub dare: Account<'info, Dare>,
     
     #[account(
         mut,
-        seeds = [b"bet", dare.key().as_ref(), winner.key().as_ref()],
+        seeds = [b"bet", dare.key().as_ref(), bettor.key().as_ref()],
         bump = bet.bump
     )]
     pub bet: Account<'info, Bet>,
@@ -508,53 +2499,132 @@ pub struct ClaimWinnings<'info> {
     /// CHECK: This is a PDA that holds SOL
     pub pool_account: AccountInfo<'info>,
     
+    /// Token dares only: the dare's mint, vault and token program
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", dare.key().as_ref()],
+        bump
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    /// Token dares only: the bettor's token account for the dare's mint
     #[account(mut)]
-    pub winner: Signer<'info>,
+    pub bettor_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Token dares only: the treasury's token account for the dare's mint
+    #[account(mut)]
+    pub treasury_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = treasury
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    /// CHECK: The treasury PDA, checked against `config.treasury`
+    pub treasury: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub bettor: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct ClaimWinnerReward<'info> {
-    #[account(mut)]
+pub struct ClaimCreatorFee<'info> {
+    #[account(
+        mut,
+        has_one = creator
+    )]
     pub dare: Account<'info, Dare>,
     
     #[account(
         mut,
-        seeds = [b"proof", dare.key().as_ref(), winner.key().as_ref()],
+        seeds = [b"pool", dare.key().as_ref()],
         bump
     )]
-    pub proof_submission: Account<'info, ProofSubmission>,
+    /// CHECK: This is a PDA that holds SOL
+    pub pool_account: AccountInfo<'info>,
+    
+    /// Token dares only: the dare's mint, vault and token program
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
     
     #[account(
         mut,
-        seeds = [b"pool", dare.key().as_ref()],
+        seeds = [b"vault", dare.key().as_ref()],
         bump
     )]
-    pub pool_account: SystemAccount<'info>,
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     
+    /// Token dares only: the creator's token account for the dare's mint
     #[account(mut)]
-    pub winner: Signer<'info>,
+    pub creator_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
     
+    #[account(mut)]
+    pub creator: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct LikeProofSubmission<'info> {
+pub struct CollectProtocolFee<'info> {
+    #[account(mut)]
+    pub dare: Account<'info, Dare>,
+    
     #[account(
         mut,
-        seeds = [b"proof", dare.key().as_ref(), proof_submission.submitter.as_ref()],
+        seeds = [b"pool", dare.key().as_ref()],
         bump
     )]
-    pub proof_submission: Account<'info, ProofSubmission>,
+    /// CHECK: This is a PDA that holds SOL
+    pub pool_account: AccountInfo<'info>,
     
-    pub dare: Account<'info, Dare>,
+    /// Token dares only: the dare's mint, vault and token program
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", dare.key().as_ref()],
+        bump
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
     
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    /// Token dares only: the treasury's token account for the dare's mint
     #[account(mut)]
-    pub liker: Signer<'info>,
+    pub treasury_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = treasury
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    /// CHECK: The treasury PDA, checked against `config.treasury`
+    pub treasury: AccountInfo<'info>,
+    
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct ClaimCompletionReward<'info> {
+pub struct SweepDust<'info> {
     #[account(mut)]
     pub dare: Account<'info, Dare>,
     
@@ -566,134 +2636,440 @@ pub struct ClaimCompletionReward<'info> {
     /// CHECK: This is a PDA that holds SOL
     pub pool_account: AccountInfo<'info>,
     
+    /// Token dares only: the dare's mint, vault and token program
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", dare.key().as_ref()],
+        bump
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    /// Token dares only: the treasury's token account for the dare's mint
     #[account(mut)]
-    pub submitter: Signer<'info>,
+    pub treasury_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = treasury
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    /// CHECK: The treasury PDA, checked against `config.treasury`
+    pub treasury: AccountInfo<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveDare<'info> {
+    #[account(mut)]
+    pub dare: Account<'info, Dare>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// CHECK: Only read for oracle dares past their resolution deadline, where
+    /// the address and owner are checked against the dare's resolution mode
+    pub oracle: Option<UncheckedAccount<'info>>,
+
+    /// The config admin during the resolution window, anyone afterwards
+    pub resolver: Signer<'info>,
+}
+
+/// The dare's approved `ProofSubmission`s are passed as remaining accounts
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeRankings<'info> {
+    #[account(mut)]
+    pub dare: Account<'info, Dare>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveFromOracle<'info> {
+    #[account(mut)]
+    pub dare: Account<'info, Dare>,
+    
+    /// CHECK: Address and owner are checked against the dare's resolution mode,
+    /// the contents by `OracleOutcome::try_from_account`
+    pub oracle: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub dare: Account<'info, Dare>,
+    
+    #[account(
+        seeds = [b"bet", dare.key().as_ref(), voter.key().as_ref()],
+        bump = bet.bump
+    )]
+    pub bet: Account<'info, Bet>,
+    
+    #[account(
+        init,
+        payer = voter,
+        space = VoteRecord::LEN,
+        seeds = [b"vote", dare.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, VoteRecord>,
+    
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct CashOutEarly<'info> {
+pub struct FinalizeCommunityVote<'info> {
     #[account(mut)]
     pub dare: Account<'info, Dare>,
     
     #[account(
-        mut,
-        seeds = [b"bet", dare.key().as_ref(), bettor.key().as_ref()],
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VoteResolution<'info> {
+    #[account(mut)]
+    pub dare: Account<'info, Dare>,
+    
+    #[account(
+        seeds = [b"committee", committee.committee_id.to_le_bytes().as_ref()],
+        bump = committee.bump
+    )]
+    pub committee: Account<'info, ResolverCommittee>,
+    
+    #[account(
+        init,
+        payer = member,
+        space = CommitteeVote::LEN,
+        seeds = [b"committee_vote", dare.key().as_ref(), member.key().as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, CommitteeVote>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub member: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(mut)]
+    pub dare: Account<'info, Dare>,
+    
+    #[account(
+        seeds = [b"bet", dare.key().as_ref(), challenger.key().as_ref()],
         bump = bet.bump
     )]
     pub bet: Account<'info, Bet>,
     
     #[account(
-        mut,
-        seeds = [b"pool", dare.key().as_ref()],
+        init,
+        payer = challenger,
+        space = Dispute::LEN,
+        seeds = [b"dispute", dare.key().as_ref()],
         bump
     )]
-    /// CHECK: This is a PDA that holds SOL
-    pub pool_account: AccountInfo<'info>,
+    pub dispute: Account<'info, Dispute>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
     
     #[account(mut)]
-    pub bettor: Signer<'info>,
+    pub challenger: Signer<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct ClaimCreatorFee<'info> {
+pub struct ResolveDispute<'info> {
+    #[account(mut)]
+    pub dare: Account<'info, Dare>,
+    
     #[account(
         mut,
-        has_one = creator
+        seeds = [b"dispute", dare.key().as_ref()],
+        bump = dispute.bump,
+        has_one = challenger
+    )]
+    pub dispute: Account<'info, Dispute>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = treasury,
+        constraint = config.disputes.arbiter == arbiter.key() @ ErrorCode::Unauthorized
     )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    /// CHECK: The treasury PDA, checked against `config.treasury`
+    pub treasury: AccountInfo<'info>,
+    
+    /// CHECK: Checked against `dispute.challenger`, receives the bond and reward
+    #[account(mut)]
+    pub challenger: AccountInfo<'info>,
+    
+    pub arbiter: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExpireDispute<'info> {
+    #[account(mut)]
     pub dare: Account<'info, Dare>,
     
     #[account(
         mut,
-        seeds = [b"pool", dare.key().as_ref()],
-        bump
+        seeds = [b"dispute", dare.key().as_ref()],
+        bump = dispute.bump,
+        has_one = challenger
     )]
-    /// CHECK: This is a PDA that holds SOL
-    pub pool_account: AccountInfo<'info>,
+    pub dispute: Account<'info, Dispute>,
     
+    /// CHECK: Checked against `dispute.challenger`, receives the bond back
     #[account(mut)]
-    pub creator: Signer<'info>,
-    pub system_program: Program<'info, System>,
+    pub challenger: AccountInfo<'info>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SelectWinners<'info> {
     #[account(mut)]
     pub dare: Account<'info, Dare>,
     
+    #[account(
+        mut,
+        constraint = first_place.dare == dare.key() @ ErrorCode::SubmissionDareMismatch,
+        constraint = first_place.is_approved @ ErrorCode::ProofNotApproved
+    )]
+    pub first_place: Account<'info, ProofSubmission>,
+    
+    #[account(
+        mut,
+        constraint = second_place.dare == dare.key() @ ErrorCode::SubmissionDareMismatch,
+        constraint = second_place.is_approved @ ErrorCode::ProofNotApproved
+    )]
+    pub second_place: Option<Account<'info, ProofSubmission>>,
+    
+    #[account(
+        mut,
+        constraint = third_place.dare == dare.key() @ ErrorCode::SubmissionDareMismatch,
+        constraint = third_place.is_approved @ ErrorCode::ProofNotApproved
+    )]
+    pub third_place: Option<Account<'info, ProofSubmission>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
     pub platform_authority: Signer<'info>,
 }
 
+/// Shared by `approve_proof` and `reject_proof`
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ApproveProof<'info> {
     #[account(mut)]
     pub dare: Account<'info, Dare>,
     
+    #[account(
+        mut,
+        seeds = [b"proof", dare.key().as_ref(), &proof_submission.submission_index.to_le_bytes()],
+        bump = proof_submission.bump
+    )]
+    pub proof_submission: Account<'info, ProofSubmission>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
     #[account(mut)]
     pub platform_authority: Signer<'info>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct EmergencyWithdraw<'info> {
+pub struct EmergencyRefund<'info> {
     #[account(mut)]
     pub dare: Account<'info, Dare>,
     
     #[account(
-        mut,
         seeds = [b"pool", dare.key().as_ref()],
         bump
     )]
-    /// CHECK: This is a PDA that holds SOL - will be drained in emergency
+    /// CHECK: This is a PDA that holds SOL, only read to snapshot the pool
     pub pool_account: AccountInfo<'info>,
     
-    /// The developer wallet - hardcoded address check in function
-    #[account(mut)]
-    pub developer: Signer<'info>,
+    /// Token dares only: the dare's mint, vault and token program
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
     
-    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"vault", dare.key().as_ref()],
+        bump
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+/// Global singleton at `[b"config"]`. Its `admin` approves proofs, ranks
+/// winners and handles emergencies for every dare; treasury withdrawals and
+/// dispute config changes go through a `PendingAction` and wait out
+/// `withdrawal_timelock_secs`.
+#[account]
+pub struct ProgramConfig {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>, // Nominated by propose_admin, must accept_admin
+    pub treasury: Pubkey, // Treasury PDA at [b"treasury"] collecting protocol revenue
+    pub default_fees: FeeSchedule, // Applied to new dares without an override
+    pub max_fees: FeeSchedule, // Upper bound for per-dare overrides
+    pub claim_window_secs: i64, // Time after settlement before unclaimed payouts can be swept
+    pub resolution_window_secs: i64, // Minimum gap between a dare's proof and resolution deadlines
+    pub disputes: DisputeConfig, // Who rules on disputes and what they cost
+    pub withdrawal_timelock_secs: i64, // Delay between queueing an admin action and executing it
+    pub dispute_reserve: u64, // Slashed bonds held in the treasury, the only source of dispute rewards
+    pub paused: u8, // PAUSE_* bits blocking instructions on every dare, set by set_paused
+    pub claims_resumed_at: i64, // When set_paused last lifted a global PAUSE_CLAIMING
+    pub bump: u8,
+}
+
+impl ProgramConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // admin
+        1 + 32 + // pending_admin (Option<Pubkey>)
+        32 + // treasury
+        FeeSchedule::LEN + // default_fees
+        FeeSchedule::LEN + // max_fees
+        8 + // claim_window_secs
+        8 + // resolution_window_secs
+        DisputeConfig::LEN + // disputes
+        8 + // withdrawal_timelock_secs
+        8 + // dispute_reserve
+        1 + // paused
+        8 + // claims_resumed_at
+        1; // bump
 }
 
 #[account]
 pub struct Dare {
     pub creator: Pubkey,
-    pub platform_authority: Pubkey, // Admin who can approve proofs and rank winners
     pub title: String,
     pub description: String,
-    pub deadline: i64,
-    pub min_bet: u64,
+    pub betting_closes_at: i64, // No bets or cash-outs from here on
+    pub proof_deadline: i64, // No proof submissions from here on; resolution can start
+    pub resolution_deadline: i64, // Admin resolves before this, afterwards anyone resolves as failed
+    pub resolution_mode: ResolutionMode, // Who decides the outcome during the resolution window
+    pub ranking_mode: RankingMode, // Who ranks the winning submissions once completed
+    pub committee_will_do_votes: u8, // Committee votes so far, per outcome
+    pub committee_wont_do_votes: u8,
+    pub community_will_do_weight: u64, // Stake behind each outcome in a community vote
+    pub community_wont_do_weight: u64,
+    pub mint: Option<Pubkey>, // Stake token for SPL / Token-2022 dares, None for SOL dares
+    pub min_bet: u64, // In lamports, or base units of the mint
+    pub fees: FeeSchedule, // Snapshot of the fee schedule at creation
     pub total_pool: u64,
     pub will_do_pool: u64,
     pub wont_do_pool: u64,
-    pub is_completed: bool,
-    pub is_expired: bool,
+    pub status: DareStatus, // Lifecycle stage, changed only through Dare::transition
+    pub paused: u8, // PAUSE_* bits blocking instructions on this dare only, set by set_paused
     pub submission_count: u32, // Track number of submissions
-    pub winners_selected: bool, // Whether admin has selected winners
-    pub first_place_winner: Option<Pubkey>, // 1st place winner
-    pub second_place_winner: Option<Pubkey>, // 2nd place winner  
-    pub third_place_winner: Option<Pubkey>, // 3rd place winner
+    pub approved_count: u32, // Submissions approved by the admin
+    pub rejected_count: u32, // Submissions rejected by the admin
+    pub first_place_winner: Option<Pubkey>, // 1st place ProofSubmission
+    pub second_place_winner: Option<Pubkey>, // 2nd place ProofSubmission
+    pub third_place_winner: Option<Pubkey>, // 3rd place ProofSubmission
     pub first_place_claimed: bool,
     pub second_place_claimed: bool,
     pub third_place_claimed: bool,
     pub creator_fee_claimed: bool,
+    pub protocol_fee_collected: bool,
+    pub total_claimed: u64, // Amount paid out of the pool since settlement
+    pub winning_stake_claimed: u64, // Winning-side stakes whose payout has been claimed
+    pub outcome: Option<BetType>, // Side chosen by resolve_dare (or by the arbiter on dispute)
+    pub settled_at: i64, // When resolve_dare froze the outcome (or the dare was cancelled)
+    pub dispute_deadline: i64, // Disputes can be opened until this, claims open from it
+    pub refunded_stake: u64, // Stakes returned through claim_refund (fees included)
+    pub emergency_pool: u64, // Left to refund after emergency_refund, split pro-rata over emergency_stake
+    pub emergency_stake: u64, // Stakes still to be refunded after emergency_refund
+    pub claims_resumed_at: i64, // When set_paused last lifted this dare's PAUSE_CLAIMING
     pub bump: u8,
 }
 
 impl Dare {
     pub const LEN: usize = 8 + // discriminator
         32 + // creator
-        32 + // platform_authority
         4 + 64 + // title (max 64 chars)
         4 + 256 + // description (max 256 chars)
-        8 + // deadline
+        8 + // betting_closes_at
+        8 + // proof_deadline
+        8 + // resolution_deadline
+        ResolutionMode::LEN + // resolution_mode
+        1 + // ranking_mode
+        1 + // committee_will_do_votes
+        1 + // committee_wont_do_votes
+        8 + // community_will_do_weight
+        8 + // community_wont_do_weight
+        1 + 32 + // mint (Option<Pubkey>)
         8 + // min_bet
+        FeeSchedule::LEN + // fees
         8 + // total_pool
         8 + // will_do_pool
         8 + // wont_do_pool
-        1 + // is_completed
-        1 + // is_expired
+        1 + // status
+        1 + // paused
         4 + // submission_count
-        1 + // winners_selected
+        4 + // approved_count
+        4 + // rejected_count
         1 + 32 + // first_place_winner (Option<Pubkey>)
         1 + 32 + // second_place_winner (Option<Pubkey>)
         1 + 32 + // third_place_winner (Option<Pubkey>)
@@ -701,16 +3077,126 @@ impl Dare {
         1 + // second_place_claimed
         1 + // third_place_claimed
         1 + // creator_fee_claimed
+        1 + // protocol_fee_collected
+        8 + // total_claimed
+        8 + // winning_stake_claimed
+        1 + 1 + // outcome (Option<BetType>)
+        8 + // settled_at
+        8 + // dispute_deadline
+        8 + // refunded_stake
+        8 + // emergency_pool
+        8 + // emergency_stake
+        8 + // claims_resumed_at
         1; // bump
 }
 
+impl Dare {
+    /// Moves the dare to `next`. Every status change goes through here so an
+    /// instruction can never skip a stage or revive a finished dare.
+    pub fn transition(&mut self, next: DareStatus) -> Result<()> {
+        require!(
+            self.status.can_transition_to(next),
+            ErrorCode::InvalidStatusTransition
+        );
+        self.status = next;
+        Ok(())
+    }
+
+    pub fn require_status(&self, allowed: &[DareStatus]) -> Result<()> {
+        require!(allowed.contains(&self.status), ErrorCode::InvalidDareStatus);
+        Ok(())
+    }
+
+    /// Applies the transitions that happen with time alone: betting closes at
+    /// `betting_closes_at`, proof submission at `proof_deadline`.
+    pub fn refresh_status(&mut self, now: i64) -> Result<()> {
+        if self.status == DareStatus::Open && now >= self.betting_closes_at {
+            self.transition(DareStatus::Locked)?;
+        }
+        if self.status == DareStatus::Locked && now >= self.proof_deadline {
+            self.transition(DareStatus::AwaitingResolution)?;
+        }
+        Ok(())
+    }
+
+    /// Freezes `outcome` and starts the dispute window
+    pub fn resolve(&mut self, outcome: BetType, now: i64, dispute_window_secs: i64) -> Result<()> {
+        self.apply_outcome(outcome)?;
+        self.settled_at = now;
+        self.dispute_deadline = now
+            .checked_add(dispute_window_secs)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Moves a resolved (or disputed) dare to the status for `outcome`. A
+    /// winning side nobody bet on sends everyone to refunds instead.
+    pub fn apply_outcome(&mut self, outcome: BetType) -> Result<()> {
+        let (winning_pool, status) = match outcome {
+            BetType::WillDo => (self.will_do_pool, DareStatus::Completed),
+            BetType::WontDo => (self.wont_do_pool, DareStatus::Failed),
+        };
+        if winning_pool == 0 {
+            self.transition(DareStatus::Refunding)?;
+        } else {
+            self.transition(status)?;
+        }
+        self.outcome = Some(outcome);
+        Ok(())
+    }
+
+    /// Fails if the instructions behind `flag` are paused globally or for this dare
+    pub fn require_not_paused(&self, config: &ProgramConfig, flag: u8) -> Result<()> {
+        require!((config.paused | self.paused) & flag == 0, ErrorCode::Paused);
+        Ok(())
+    }
+
+    /// Claims wait for the dispute window to pass
+    pub fn require_claims_open(&self, now: i64) -> Result<()> {
+        require!(now >= self.dispute_deadline, ErrorCode::DisputeWindowOpen);
+        Ok(())
+    }
+
+    /// When unclaimed payouts can be swept: a full claim window after claims
+    /// open, or after they were last resumed from a pause, globally or on this dare
+    pub fn claim_window_ends(&self, config: &ProgramConfig) -> Result<i64> {
+        let claims_open_since = self
+            .dispute_deadline
+            .max(self.claims_resumed_at)
+            .max(config.claims_resumed_at);
+        Ok(claims_open_since
+            .checked_add(config.claim_window_secs)
+            .ok_or(ErrorCode::MathOverflow)?)
+    }
+
+    pub fn winners_selected(&self) -> bool {
+        self.first_place_winner.is_some()
+    }
+
+    /// The side that wins the pool, or `None` unless the dare settled with a winner
+    pub fn winning_side(&self) -> Option<BetType> {
+        match self.status {
+            DareStatus::Completed => Some(BetType::WillDo),
+            DareStatus::Failed => Some(BetType::WontDo),
+            _ => None,
+        }
+    }
+
+    /// True once bettors can only get their stakes back
+    pub fn is_refund_only(&self) -> bool {
+        matches!(self.status, DareStatus::Refunding | DareStatus::Cancelled)
+    }
+}
+
+/// A bettor's position on one dare, at `[b"bet", dare, bettor]`.
+/// Every `place_bet` adds to the side it names.
 #[account]
 pub struct Bet {
     pub dare: Pubkey,
     pub bettor: Pubkey,
-    pub amount: u64,
-    pub bet_type: BetType,
-    pub bet_timestamp: i64, // When the bet was placed
+    pub will_do_amount: u64,
+    pub wont_do_amount: u64,
+    pub bet_timestamp: i64, // First deposit into the current position; starts the cash-out window
     pub is_claimed: bool,
     pub bump: u8,
 }
@@ -719,11 +3205,36 @@ impl Bet {
     pub const LEN: usize = 8 + // discriminator
         32 + // dare
         32 + // bettor
-        8 + // amount
-        1 + // bet_type
+        8 + // will_do_amount
+        8 + // wont_do_amount
         8 + // bet_timestamp
         1 + // is_claimed
         1; // bump
+
+    /// How long after its first deposit a position can be cashed out
+    pub const CASH_OUT_WINDOW_SECS: i64 = 600;
+
+    /// Stake across both sides
+    pub fn total_amount(&self) -> Result<u64> {
+        payout::add(self.will_do_amount, self.wont_do_amount)
+    }
+
+    /// Adds `amount` to the `bet_type` side. Only the first deposit into an
+    /// empty position starts the cash-out window, so top-ups can't extend it.
+    pub fn deposit(&mut self, bet_type: BetType, amount: u64, now: i64) -> Result<()> {
+        if self.total_amount()? == 0 {
+            self.bet_timestamp = now;
+        }
+        match bet_type {
+            BetType::WillDo => self.will_do_amount = payout::add(self.will_do_amount, amount)?,
+            BetType::WontDo => self.wont_do_amount = payout::add(self.wont_do_amount, amount)?,
+        }
+        Ok(())
+    }
+
+    pub fn can_cash_out(&self, now: i64) -> bool {
+        now.saturating_sub(self.bet_timestamp) <= Self::CASH_OUT_WINDOW_SECS
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -737,17 +3248,23 @@ pub struct CompletionProof {
     pub approval_timestamp: Option<i64>,
 }
 
+/// Addressed by `[b"proof", dare, submission_index.to_le_bytes()]`, where
+/// `submission_index` is the dare's `submission_count` at submission time.
+/// See `pda::find_proof_submission_address`.
 #[account]
 pub struct ProofSubmission {
     pub dare: Pubkey,
     pub submitter: Pubkey,
+    pub submission_index: u32, // Position in the dare's submission sequence (PDA seed)
     pub proof_hash: String,
     pub proof_description: String,
     pub submission_timestamp: i64,
     pub is_approved: bool, // Admin approval
-    pub approved_by: Pubkey,
-    pub approval_timestamp: i64,
-    pub likes_count: u32, // Social engagement metric
+    pub is_rejected: bool, // Admin rejection
+    pub rejection_reason: u8, // Moderator reason code, 0 = not rejected
+    pub approved_by: Pubkey, // Reviewer (approval or rejection)
+    pub approval_timestamp: i64, // Review time (approval or rejection)
+    pub likes_count: u32, // Distinct wallets with a Like on this submission
     pub is_winner: bool, // Whether this submission won (1st, 2nd, or 3rd)
     pub winner_rank: u8, // 1 = first, 2 = second, 3 = third, 0 = not winner
     pub reward_claimed: bool,
@@ -758,10 +3275,13 @@ impl ProofSubmission {
     pub const LEN: usize = 8 + // discriminator
         32 + // dare
         32 + // submitter
+        4 + // submission_index
         4 + 64 + // proof_hash (max 64 chars)
         4 + 256 + // proof_description (max 256 chars)
         8 + // submission_timestamp
         1 + // is_approved
+        1 + // is_rejected
+        1 + // rejection_reason
         32 + // approved_by
         8 + // approval_timestamp
         4 + // likes_count
@@ -771,19 +3291,364 @@ impl ProofSubmission {
         1; // bump
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+/// A wallet's like on a proof submission, at `[b"like", proof_submission, liker]`
+#[account]
+pub struct Like {
+    pub proof_submission: Pubkey,
+    pub liker: Pubkey,
+    pub liked_at: i64,
+    pub bump: u8,
+}
+
+impl Like {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // proof_submission
+        32 + // liker
+        8 + // liked_at
+        1; // bump
+}
+
+/// An open challenge against a dare's outcome, at `[b"dispute", dare]`.
+/// Holds the challenger's bond until `resolve_dispute` or `expire_dispute` closes it.
+#[account]
+pub struct Dispute {
+    pub dare: Pubkey,
+    pub challenger: Pubkey,
+    pub bond: u64, // Lamports posted by the challenger, held in this account
+    pub opened_at: i64,
+    pub ruling_deadline: i64, // The arbiter rules before this, afterwards anyone can expire the dispute
+    pub bump: u8,
+}
+
+impl Dispute {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // dare
+        32 + // challenger
+        8 + // bond
+        8 + // opened_at
+        8 + // ruling_deadline
+        1; // bump
+}
+
+/// M-of-N resolver for high-value dares, at `[b"committee", committee_id.to_le_bytes()]`.
+/// Created by the admin; dares opt in through `ResolutionMode::Committee`.
+#[account]
+pub struct ResolverCommittee {
+    pub committee_id: u64,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8, // Matching votes needed to resolve a dare
+    pub bump: u8,
+}
+
+impl ResolverCommittee {
+    pub const MAX_MEMBERS: usize = 10;
+
+    pub const LEN: usize = 8 + // discriminator
+        8 + // committee_id
+        4 + 32 * Self::MAX_MEMBERS + // members
+        1 + // threshold
+        1; // bump
+}
+
+/// One member's vote on a dare, at `[b"committee_vote", dare, member]`
+#[account]
+pub struct CommitteeVote {
+    pub dare: Pubkey,
+    pub member: Pubkey,
+    pub outcome: BetType,
+    pub voted_at: i64,
+    pub bump: u8,
+}
+
+impl CommitteeVote {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // dare
+        32 + // member
+        1 + // outcome
+        8 + // voted_at
+        1; // bump
+}
+
+/// A bettor's stake-weighted vote on a community-resolved dare, at `[b"vote", dare, voter]`
+#[account]
+pub struct VoteRecord {
+    pub dare: Pubkey,
+    pub voter: Pubkey,
+    pub outcome: BetType,
+    pub weight: u64, // The voter's total stake when voting
+    pub voted_at: i64,
+    pub bump: u8,
+}
+
+impl VoteRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // dare
+        32 + // voter
+        1 + // outcome
+        8 + // weight
+        8 + // voted_at
+        1; // bump
+}
+
+/// Allowlist entry for a token dares can be staked in, at `[b"allowed_mint", mint]`.
+/// Managed by the admin with `set_allowed_mint` / `remove_allowed_mint`.
+#[account]
+pub struct AllowedMint {
+    pub mint: Pubkey,
+    pub decimals: u8, // Must match the mint, guards against listing the wrong token
+    pub min_bet: u64, // Lowest `min_bet` a dare in this mint may set
+    pub max_bet: u64, // Largest position a bettor may hold in one dare
+    pub fee_override: Option<FeeSchedule>, // Replaces the config's default fees for this mint
+    pub bump: u8,
+}
+
+impl AllowedMint {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        1 + // decimals
+        8 + // min_bet
+        8 + // max_bet
+        1 + FeeSchedule::LEN + // fee_override (Option<FeeSchedule>)
+        1; // bump
+
+    /// The allowlist entry passed for `mint`, which token dares must have
+    pub fn require_for<'a>(
+        entry: &'a Option<Account<'_, AllowedMint>>,
+        mint: Pubkey,
+    ) -> Result<&'a AllowedMint> {
+        match entry {
+            Some(allowed_mint) if allowed_mint.mint == mint => Ok(allowed_mint),
+            _ => err!(ErrorCode::MintNotAllowed),
+        }
+    }
+}
+
+/// Dispute settings in `ProgramConfig`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DisputeConfig {
+    pub arbiter: Pubkey, // Rules on disputes with resolve_dispute
+    pub window_secs: i64, // How long after resolution a dispute can be opened
+    pub ruling_window_secs: i64, // How long the arbiter has to rule once a dispute is opened
+    pub bond: u64, // Lamports a challenger posts, slashed if the outcome stands
+    pub reward: u64, // Paid from the dispute reserve on top of the bond when the outcome is overturned
+}
+
+impl DisputeConfig {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.window_secs > 0 && self.ruling_window_secs > 0 && self.bond > 0,
+            ErrorCode::InvalidDisputeConfig
+        );
+        Ok(())
+    }
+}
+
+/// Fee and reward split for a dare, in basis points.
+/// Winner rewards are a share of the pool left after the creator and protocol fees.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FeeSchedule {
+    pub creator_fee_bps: u16,
+    pub protocol_fee_bps: u16, // Platform revenue, sent to the treasury
+    pub first_place_bps: u16,
+    pub second_place_bps: u16,
+    pub third_place_bps: u16,
+    pub cash_out_penalty_bps: u16,
+    pub cash_out_treasury_bps: u16, // Share of the cash-out penalty sent to the treasury, the rest stays in the pool
+    pub refund_fee_bps: u16, // Taken from refunded stakes and sent to the treasury
+}
+
+impl FeeSchedule {
+    pub const LEN: usize = 2 + // creator_fee_bps
+        2 + // protocol_fee_bps
+        2 + // first_place_bps
+        2 + // second_place_bps
+        2 + // third_place_bps
+        2 + // cash_out_penalty_bps
+        2 + // cash_out_treasury_bps
+        2; // refund_fee_bps
+
+    pub fn winner_rewards_bps(&self) -> u16 {
+        self.first_place_bps + self.second_place_bps + self.third_place_bps
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let bps_total = BPS_DENOMINATOR as u32;
+        require!(
+            self.creator_fee_bps as u32 + self.protocol_fee_bps as u32 <= bps_total
+                && self.cash_out_penalty_bps as u32 <= bps_total
+                && self.cash_out_treasury_bps as u32 <= bps_total
+                && self.refund_fee_bps as u32 <= bps_total
+                && self.first_place_bps as u32
+                    + self.second_place_bps as u32
+                    + self.third_place_bps as u32
+                    <= bps_total,
+            ErrorCode::InvalidFeeBps
+        );
+        Ok(())
+    }
+
+    /// Every component is at or below the matching component of `max`
+    pub fn is_within(&self, max: &FeeSchedule) -> bool {
+        self.creator_fee_bps <= max.creator_fee_bps
+            && self.protocol_fee_bps <= max.protocol_fee_bps
+            && self.first_place_bps <= max.first_place_bps
+            && self.second_place_bps <= max.second_place_bps
+            && self.third_place_bps <= max.third_place_bps
+            && self.cash_out_penalty_bps <= max.cash_out_penalty_bps
+            && self.cash_out_treasury_bps <= max.cash_out_treasury_bps
+            && self.refund_fee_bps <= max.refund_fee_bps
+    }
+}
+
+/// The time windows passed to `create_dare` (unix timestamps)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DareSchedule {
+    pub betting_closes_at: i64,
+    pub proof_deadline: i64,
+    pub resolution_deadline: i64,
+}
+
+impl DareSchedule {
+    /// Betting closes first, then proof submission, and the admin gets at least
+    /// `min_resolution_window` seconds after that to resolve
+    pub fn validate(&self, now: i64, min_resolution_window: i64) -> Result<()> {
+        let min_resolution_deadline = self
+            .proof_deadline
+            .checked_add(min_resolution_window)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            now < self.betting_closes_at
+                && self.betting_closes_at <= self.proof_deadline
+                && self.resolution_deadline >= min_resolution_deadline,
+            ErrorCode::InvalidDareSchedule
+        );
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
 pub enum BetType {
     WillDo,
     WontDo,
 }
 
+/// What `set_paused` applies to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PauseScope {
+    Global, // ProgramConfig::paused, every dare
+    Dare, // Dare::paused, the dare passed to set_paused
+}
+
+/// An admin action waiting out the config's `withdrawal_timelock_secs`, at
+/// `[b"pending_action", config, action_id.to_le_bytes()]`. Closed when it is
+/// executed or cancelled.
+#[account]
+pub struct PendingAction {
+    pub action_id: u64, // Picked by the admin, unique among pending actions
+    pub kind: PendingActionKind,
+    pub queued_at: i64,
+    pub executable_at: i64, // queued_at plus the timelock at the time of queueing
+    pub bump: u8,
+}
+
+impl PendingAction {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // action_id
+        PendingActionKind::LEN + // kind
+        8 + // queued_at
+        8 + // executable_at
+        1; // bump
+}
+
+/// What a `PendingAction` does when executed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PendingActionKind {
+    // SOL when `mint` is None; `recipient` is a wallet for SOL, a token account for tokens
+    TreasuryWithdrawal { mint: Option<Pubkey>, recipient: Pubkey, amount: u64 },
+    // Replaces `ProgramConfig::disputes`
+    DisputeConfigUpdate { disputes: DisputeConfig },
+}
+
+impl PendingActionKind {
+    const TREASURY_WITHDRAWAL_LEN: usize = (1 + 32) + 32 + 8;
+
+    // Tag plus the largest variant
+    pub const LEN: usize = 1 + if Self::TREASURY_WITHDRAWAL_LEN > DisputeConfig::LEN {
+        Self::TREASURY_WITHDRAWAL_LEN
+    } else {
+        DisputeConfig::LEN
+    };
+}
+
+/// Who resolves a dare before its `resolution_deadline`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResolutionMode {
+    Admin, // The config admin calls resolve_dare
+    Committee { committee: Pubkey }, // Members of this ResolverCommittee vote
+    Oracle { oracle: Pubkey, program: Pubkey }, // This account, owned by this program, reports the outcome
+    Community, // Bettors vote, weighted by stake, and anyone finalizes the tally
+}
+
+impl ResolutionMode {
+    pub const LEN: usize = 1 + 32 + 32;
+}
+
+/// Who ranks a completed dare's approved submissions, fixed at creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RankingMode {
+    Admin, // The config admin calls select_winners
+    Likes, // Anyone calls finalize_rankings, which ranks by likes
+}
+
+/// Lifecycle of a dare. Allowed moves are listed in `can_transition_to`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DareStatus {
+    Open, // Accepting bets and proofs
+    Locked, // Betting closed, proofs still accepted
+    AwaitingResolution, // Proof deadline passed, outcome not decided yet
+    Completed, // Resolved: "will do" bettors and ranked submissions win
+    Failed, // Resolved: "won't do" bettors win
+    Disputed, // Outcome challenged, claims frozen until the arbiter rules or the dispute expires
+    Cancelled, // Cancelled by the admin after bets came in: stakes are refunded
+    Refunding, // Settled with nobody on the winning side: stakes are refunded
+    EmergencyRefunding, // Halted by emergency_refund: bettors get the pool back pro-rata
+    Closed, // Pool swept to the treasury, no further claims
+}
+
+impl DareStatus {
+    pub fn can_transition_to(self, next: DareStatus) -> bool {
+        use DareStatus::*;
+        match self {
+            Open => matches!(next, Locked | Cancelled | EmergencyRefunding),
+            Locked => matches!(next, AwaitingResolution | Cancelled | EmergencyRefunding),
+            AwaitingResolution => matches!(
+                next,
+                Completed | Failed | Refunding | Cancelled | EmergencyRefunding
+            ),
+            Completed | Failed => {
+                matches!(next, Disputed | Cancelled | Closed | EmergencyRefunding)
+            }
+            // A challenger's bond is at stake: the arbiter rules first, unless the
+            // dare is halted (expire_dispute then returns the bond)
+            Disputed => matches!(next, Completed | Failed | Refunding | EmergencyRefunding),
+            Refunding => matches!(next, Disputed | Closed | EmergencyRefunding),
+            Cancelled => matches!(next, Closed | EmergencyRefunding),
+            EmergencyRefunding => matches!(next, Closed),
+            Closed => false,
+        }
+    }
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Bet amount is below minimum")]
     BetTooLow,
-    #[msg("Dare has expired")]
+    // Unused since dares follow DareStatus; kept so the codes after them don't shift
+    #[msg("Deprecated: dare has expired")]
     DareExpired,
-    #[msg("Dare is already completed")]
+    #[msg("Deprecated: dare is already completed")]
     DareAlreadyCompleted,
     #[msg("Winnings have already been claimed")]
     AlreadyClaimed,
@@ -791,13 +3656,14 @@ pub enum ErrorCode {
     DareNotFinalized,
     #[msg("No payout available")]
     NoPayout,
-    #[msg("Dare is not completed")]
+    // Unused, see DareExpired
+    #[msg("Deprecated: dare is not completed")]
     DareNotCompleted,
     #[msg("Not the submitter of the proof")]
     NotSubmitter,
     #[msg("Creator fee has already been claimed")]
     CreatorFeeAlreadyClaimed,
-    #[msg("Cash out window expired - only available for 10 minutes after placing bet")]
+    #[msg("Cash out window expired - only available for 10 minutes after the first bet")]
     CashOutTooLate,
     #[msg("No proof has been submitted")]
     NoProofSubmitted,
@@ -813,10 +3679,444 @@ pub enum ErrorCode {
     WinnersAlreadySelected,
     #[msg("Unauthorized platform authority")]
     UnauthorizedPlatformAuthority,
-    #[msg("Invalid developer key format")]
-    InvalidDeveloperKey,
-    #[msg("🚨 UNAUTHORIZED: Only developer can perform emergency withdrawal")]
-    UnauthorizedEmergencyWithdrawal,
-    #[msg("Pool is empty - nothing to withdraw")]
-    EmptyPool,
-}
\ No newline at end of file
+    #[msg("Proof has already been approved or rejected")]
+    ProofAlreadyReviewed,
+    #[msg("Rejection reason code must be non-zero")]
+    InvalidRejectionReason,
+    #[msg("Proof submission does not belong to this dare")]
+    SubmissionDareMismatch,
+    #[msg("The same submission cannot win more than one place")]
+    DuplicateWinner,
+    #[msg("Fee must be between 0 and 10000 basis points")]
+    InvalidFeeBps,
+    #[msg("Fee schedule exceeds the configured limits")]
+    FeeAboveLimit,
+    #[msg("Protocol fee has already been collected")]
+    ProtocolFeeAlreadyCollected,
+    #[msg("Treasury does not hold enough funds")]
+    InsufficientTreasuryFunds,
+    #[msg("Arithmetic overflow or underflow")]
+    MathOverflow,
+    #[msg("Claim window must be positive")]
+    InvalidClaimWindow,
+    #[msg("Claims are still outstanding and the claim window has not elapsed")]
+    ClaimsOutstanding,
+    #[msg("Pool balance is below its unclaimed obligations")]
+    PoolInvariantViolated,
+    #[msg("Only the admin can cancel a dare that has bets")]
+    CancelRequiresAdmin,
+    #[msg("Dare has already started paying out")]
+    DareAlreadySettled,
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("Instruction is paused")]
+    Paused,
+    #[msg("Instruction is not allowed in the dare's current status")]
+    InvalidDareStatus,
+    #[msg("Invalid dare status transition")]
+    InvalidStatusTransition,
+    #[msg("Resolution window must be positive")]
+    InvalidResolutionWindow,
+    #[msg("Dispute window, ruling window and bond must be positive")]
+    InvalidDisputeConfig,
+    #[msg("Only bettors on this dare can dispute it")]
+    NotABettor,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Claims open once the dispute window has passed")]
+    DisputeWindowOpen,
+    #[msg("Committee needs 1 to 10 distinct members and a majority threshold of at most the member count")]
+    InvalidCommittee,
+    #[msg("Signer is not a member of the committee")]
+    NotCommitteeMember,
+    #[msg("Committee does not resolve this dare")]
+    CommitteeMismatch,
+    #[msg("This dare is resolved by its committee")]
+    ResolvedByCommittee,
+    #[msg("Some submissions have not been approved or rejected yet")]
+    ProofsPendingReview,
+    #[msg("Remaining accounts must be exactly the dare's approved submissions")]
+    IncompleteSubmissionSet,
+    #[msg("Resolution window has closed")]
+    VotingClosed,
+    #[msg("This dare is resolved by a community vote")]
+    ResolvedByCommunity,
+    #[msg("This dare is not resolved by a community vote")]
+    NotCommunityResolved,
+    #[msg("Voting is still open")]
+    VotingStillOpen,
+    #[msg("This dare is resolved by its oracle")]
+    ResolvedByOracle,
+    #[msg("This dare is not resolved by an oracle")]
+    NotOracleResolved,
+    #[msg("Oracle account does not match the dare or is not an OracleOutcome")]
+    InvalidOracleAccount,
+    #[msg("Oracle has not reported an outcome yet")]
+    OracleNotResolved,
+    #[msg("Dare windows must satisfy now < betting close <= proof deadline and leave the admin resolution window")]
+    InvalidDareSchedule,
+    #[msg("Token dares need the mint, vault, token program and token accounts")]
+    MissingTokenAccounts,
+    #[msg("Token account or mint does not match the dare's mint")]
+    MintMismatch,
+    #[msg("Vault is not owned by the dare's pool")]
+    InvalidVault,
+    #[msg("Token account does not belong to the recipient")]
+    InvalidTokenAccount,
+    #[msg("Mint is not on the allowlist")]
+    MintNotAllowed,
+    #[msg("Mint decimals must match and bet limits need 0 < min_bet <= max_bet")]
+    InvalidMintConfig,
+    #[msg("Bet exceeds the mint's maximum position")]
+    BetTooHigh,
+    #[msg("Timelock must be positive and can only be lengthened")]
+    InvalidTimelock,
+    #[msg("Pending action is still timelocked")]
+    TimelockNotElapsed,
+    #[msg("Accounts do not match the pending action")]
+    PendingActionMismatch,
+    #[msg("Pause flags contain unknown bits")]
+    InvalidPauseFlags,
+    #[msg("Pausing a single dare needs the dare account")]
+    MissingPausedDare,
+    #[msg("Amount must be positive")]
+    InvalidAmount,
+    #[msg("This dare's winners are ranked by the admin")]
+    RankedByAdmin,
+    #[msg("This dare's winners are ranked by likes")]
+    RankedByLikes,
+    #[msg("The arbiter's ruling window has closed")]
+    RulingWindowClosed,
+    #[msg("The arbiter can still rule on this dispute")]
+    RulingWindowOpen,
+    #[msg("Winner places must be filled in order, one per approved submission up to three")]
+    UnfilledWinnerPlace,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use DareStatus::*;
+
+    const ALL_STATUSES: [DareStatus; 10] = [
+        Open,
+        Locked,
+        AwaitingResolution,
+        Completed,
+        Failed,
+        Disputed,
+        Cancelled,
+        Refunding,
+        EmergencyRefunding,
+        Closed,
+    ];
+
+    /// Every allowed move; anything not listed must be rejected
+    const ALLOWED_TRANSITIONS: [(DareStatus, &[DareStatus]); 10] = [
+        (Open, &[Locked, Cancelled, EmergencyRefunding]),
+        (Locked, &[AwaitingResolution, Cancelled, EmergencyRefunding]),
+        (
+            AwaitingResolution,
+            &[Completed, Failed, Refunding, Cancelled, EmergencyRefunding],
+        ),
+        (Completed, &[Disputed, Cancelled, Closed, EmergencyRefunding]),
+        (Failed, &[Disputed, Cancelled, Closed, EmergencyRefunding]),
+        (Disputed, &[Completed, Failed, Refunding, EmergencyRefunding]),
+        (Cancelled, &[Closed, EmergencyRefunding]),
+        (Refunding, &[Disputed, Closed, EmergencyRefunding]),
+        (EmergencyRefunding, &[Closed]),
+        (Closed, &[]),
+    ];
+
+    /// An open SOL dare with no bets: betting closes at 500, proofs at 1_000
+    /// and resolution at 2_000
+    pub(crate) fn test_dare() -> Dare {
+        Dare {
+            creator: Pubkey::default(),
+            title: String::new(),
+            description: String::new(),
+            betting_closes_at: 500,
+            proof_deadline: 1_000,
+            resolution_deadline: 2_000,
+            resolution_mode: ResolutionMode::Admin,
+            ranking_mode: RankingMode::Admin,
+            committee_will_do_votes: 0,
+            committee_wont_do_votes: 0,
+            community_will_do_weight: 0,
+            community_wont_do_weight: 0,
+            mint: None,
+            min_bet: 0,
+            fees: FeeSchedule {
+                creator_fee_bps: 0,
+                protocol_fee_bps: 0,
+                first_place_bps: 0,
+                second_place_bps: 0,
+                third_place_bps: 0,
+                cash_out_penalty_bps: 0,
+                cash_out_treasury_bps: 0,
+                refund_fee_bps: 0,
+            },
+            total_pool: 0,
+            will_do_pool: 0,
+            wont_do_pool: 0,
+            status: Open,
+            paused: 0,
+            submission_count: 0,
+            approved_count: 0,
+            rejected_count: 0,
+            first_place_winner: None,
+            second_place_winner: None,
+            third_place_winner: None,
+            first_place_claimed: false,
+            second_place_claimed: false,
+            third_place_claimed: false,
+            creator_fee_claimed: false,
+            protocol_fee_collected: false,
+            total_claimed: 0,
+            winning_stake_claimed: 0,
+            outcome: None,
+            settled_at: 0,
+            dispute_deadline: 0,
+            refunded_stake: 0,
+            emergency_pool: 0,
+            emergency_stake: 0,
+            claims_resumed_at: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn transitions_match_the_table() {
+        for (from, allowed) in ALLOWED_TRANSITIONS {
+            for next in ALL_STATUSES {
+                assert_eq!(
+                    from.can_transition_to(next),
+                    allowed.contains(&next),
+                    "{from:?} -> {next:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn transition_rejects_forbidden_moves() {
+        for (from, allowed) in ALLOWED_TRANSITIONS {
+            for next in ALL_STATUSES {
+                let mut dare = Dare { status: from, ..test_dare() };
+                let result = dare.transition(next);
+                if allowed.contains(&next) {
+                    assert!(result.is_ok(), "{from:?} -> {next:?}");
+                    assert_eq!(dare.status, next);
+                } else {
+                    assert_eq!(
+                        result.unwrap_err(),
+                        ErrorCode::InvalidStatusTransition.into(),
+                        "{from:?} -> {next:?}"
+                    );
+                    assert_eq!(dare.status, from);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn refresh_status_follows_the_schedule() {
+        let cases = [
+            (Open, 499, Open),
+            (Open, 500, Locked),
+            (Open, 999, Locked),
+            (Open, 1_000, AwaitingResolution),
+            (Open, 5_000, AwaitingResolution),
+            (Locked, 999, Locked),
+            (Locked, 1_000, AwaitingResolution),
+            (AwaitingResolution, 5_000, AwaitingResolution),
+        ];
+        for (from, now, expected) in cases {
+            let mut dare = Dare { status: from, ..test_dare() };
+            dare.refresh_status(now).unwrap();
+            assert_eq!(dare.status, expected, "{from:?} at {now}");
+        }
+    }
+
+    #[test]
+    fn refresh_status_leaves_later_statuses_alone() {
+        for from in ALL_STATUSES {
+            if matches!(from, Open | Locked) {
+                continue;
+            }
+            let mut dare = Dare { status: from, ..test_dare() };
+            dare.refresh_status(i64::MAX).unwrap();
+            assert_eq!(dare.status, from);
+        }
+    }
+
+    fn fees(bps: u16) -> FeeSchedule {
+        FeeSchedule {
+            creator_fee_bps: bps,
+            protocol_fee_bps: bps,
+            first_place_bps: bps,
+            second_place_bps: bps,
+            third_place_bps: bps,
+            cash_out_penalty_bps: bps,
+            cash_out_treasury_bps: bps,
+            refund_fee_bps: bps,
+        }
+    }
+
+    #[test]
+    fn fee_schedules_stay_within_100_percent() {
+        assert!(fees(3_333).validate().is_ok());
+        assert!(fees(0).validate().is_ok());
+
+        let too_much = [
+            FeeSchedule { creator_fee_bps: 5_001, protocol_fee_bps: 5_000, ..fees(0) },
+            FeeSchedule { first_place_bps: 3_335, ..fees(3_333) },
+            FeeSchedule { cash_out_penalty_bps: 10_001, ..fees(0) },
+            FeeSchedule { cash_out_treasury_bps: 10_001, ..fees(0) },
+            FeeSchedule { refund_fee_bps: 10_001, ..fees(0) },
+        ];
+        for schedule in too_much {
+            assert_eq!(
+                schedule.validate().unwrap_err(),
+                ErrorCode::InvalidFeeBps.into(),
+                "{schedule:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn overrides_must_stay_below_every_limit() {
+        let max = fees(1_000);
+        assert!(fees(1_000).is_within(&max));
+        assert!(fees(0).is_within(&max));
+        assert!(!FeeSchedule { creator_fee_bps: 1_001, ..fees(0) }.is_within(&max));
+        assert!(!FeeSchedule { refund_fee_bps: 1_001, ..fees(0) }.is_within(&max));
+    }
+
+    fn empty_bet() -> Bet {
+        Bet {
+            dare: Pubkey::new_unique(),
+            bettor: Pubkey::new_unique(),
+            will_do_amount: 0,
+            wont_do_amount: 0,
+            bet_timestamp: 0,
+            is_claimed: false,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn top_ups_keep_the_cash_out_window() {
+        let mut bet = empty_bet();
+        bet.deposit(BetType::WillDo, 100, 1_000).unwrap();
+        bet.deposit(BetType::WontDo, 50, 1_500).unwrap();
+        bet.deposit(BetType::WillDo, 25, 1_590).unwrap();
+
+        assert_eq!((bet.will_do_amount, bet.wont_do_amount), (125, 50));
+        assert_eq!(bet.bet_timestamp, 1_000);
+        assert!(bet.can_cash_out(1_000 + Bet::CASH_OUT_WINDOW_SECS));
+        assert!(!bet.can_cash_out(1_000 + Bet::CASH_OUT_WINDOW_SECS + 1));
+    }
+
+    #[test]
+    fn a_new_position_starts_a_new_window() {
+        let mut bet = empty_bet();
+        bet.deposit(BetType::WillDo, 100, 1_000).unwrap();
+
+        // Cashing out empties the position
+        bet.will_do_amount = 0;
+        bet.deposit(BetType::WontDo, 100, 5_000).unwrap();
+        assert_eq!(bet.bet_timestamp, 5_000);
+        assert!(bet.can_cash_out(5_000 + Bet::CASH_OUT_WINDOW_SECS));
+    }
+
+    #[test]
+    fn deposits_reject_overflow() {
+        let mut bet = empty_bet();
+        bet.deposit(BetType::WillDo, u64::MAX, 1_000).unwrap();
+        assert!(bet.deposit(BetType::WillDo, 1, 1_001).is_err());
+    }
+
+    /// A config with a 100 second claim window and nothing paused
+    fn test_config() -> ProgramConfig {
+        ProgramConfig {
+            admin: Pubkey::default(),
+            pending_admin: None,
+            treasury: Pubkey::default(),
+            default_fees: fees(0),
+            max_fees: fees(0),
+            claim_window_secs: 100,
+            resolution_window_secs: 100,
+            disputes: DisputeConfig {
+                arbiter: Pubkey::default(),
+                window_secs: 0,
+                ruling_window_secs: 0,
+                bond: 0,
+                reward: 0,
+            },
+            withdrawal_timelock_secs: 0,
+            dispute_reserve: 0,
+            paused: 0,
+            claims_resumed_at: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn resuming_claims_restarts_the_claim_window() {
+        let settled = || Dare { dispute_deadline: 3_000, ..test_dare() };
+        let resumed_globally = |at| ProgramConfig { claims_resumed_at: at, ..test_config() };
+
+        assert_eq!(settled().claim_window_ends(&test_config()).unwrap(), 3_100);
+        // A pause lifted before claims opened doesn't matter
+        assert_eq!(settled().claim_window_ends(&resumed_globally(2_000)).unwrap(), 3_100);
+        // Lifted later, in either scope, it restarts the window
+        assert_eq!(settled().claim_window_ends(&resumed_globally(5_000)).unwrap(), 5_100);
+        let resumed_on_dare = Dare { claims_resumed_at: 4_000, ..settled() };
+        assert_eq!(resumed_on_dare.claim_window_ends(&test_config()).unwrap(), 4_100);
+        assert_eq!(resumed_on_dare.claim_window_ends(&resumed_globally(5_000)).unwrap(), 5_100);
+    }
+
+    #[test]
+    fn only_lifting_a_claims_pause_resumes_claims() {
+        assert!(resumes_claims(PAUSE_CLAIMING, 0));
+        assert!(resumes_claims(PAUSE_ALL, PAUSE_BETTING));
+        assert!(!resumes_claims(PAUSE_CLAIMING, PAUSE_ALL));
+        assert!(!resumes_claims(PAUSE_BETTING, 0));
+        assert!(!resumes_claims(0, PAUSE_CLAIMING));
+    }
+
+    fn schedule(betting_closes_at: i64, proof_deadline: i64, resolution_deadline: i64) -> DareSchedule {
+        DareSchedule { betting_closes_at, proof_deadline, resolution_deadline }
+    }
+
+    #[test]
+    fn schedules_run_betting_then_proof_then_resolution() {
+        // now = 100, and the admin needs at least 50 seconds to resolve
+        assert!(schedule(200, 300, 400).validate(100, 50).is_ok());
+        assert!(schedule(101, 101, 151).validate(100, 50).is_ok());
+
+        let invalid = [
+            schedule(100, 300, 400), // betting already closed
+            schedule(50, 300, 400),
+            schedule(300, 200, 400), // proof deadline before betting closes
+            schedule(200, 300, 349), // resolution window too short
+            schedule(200, 300, 250),
+        ];
+        for schedule in invalid {
+            assert_eq!(
+                schedule.validate(100, 50).unwrap_err(),
+                ErrorCode::InvalidDareSchedule.into(),
+                "{schedule:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn resolution_window_overflow_is_rejected() {
+        assert_eq!(
+            schedule(200, i64::MAX, i64::MAX).validate(100, 1).unwrap_err(),
+            ErrorCode::MathOverflow.into()
+        );
+    }
+}
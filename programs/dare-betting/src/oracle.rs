@@ -0,0 +1,90 @@
+//! Reading outcomes published by an external oracle program.
+//!
+//! A dare in `ResolutionMode::Oracle` names an account and the program that
+//! must own it. The account holds an Anchor-serialized `OracleOutcome`: the
+//! usual 8-byte discriminator (the first bytes of `sha256("account:OracleOutcome")`)
+//! followed by the fields below, in order.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::{BetType, ErrorCode};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OracleOutcome {
+    pub authority: Pubkey, // Whoever reports the outcome; not checked here
+    pub is_resolved: bool,
+    pub will_do: bool, // Whether the dare was completed, once resolved
+    pub resolved_at: i64,
+}
+
+impl OracleOutcome {
+    pub fn discriminator() -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash(b"account:OracleOutcome").to_bytes()[..8]);
+        discriminator
+    }
+
+    /// Deserializes the outcome after checking the account's discriminator.
+    /// The caller checks the account's address and owner.
+    pub fn try_from_account(account: &AccountInfo) -> Result<Self> {
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == Self::discriminator(),
+            ErrorCode::InvalidOracleAccount
+        );
+        Self::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::InvalidOracleAccount))
+    }
+
+    /// The winning side, or `None` while the oracle hasn't reported
+    pub fn outcome(&self) -> Option<BetType> {
+        match (self.is_resolved, self.will_do) {
+            (false, _) => None,
+            (true, true) => Some(BetType::WillDo),
+            (true, false) => Some(BetType::WontDo),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    #[test]
+    fn reads_mock_oracle_accounts() {
+        assert_eq!(
+            OracleOutcome::discriminator(),
+            mock_oracle::OracleOutcome::DISCRIMINATOR
+        );
+
+        let mock = mock_oracle::OracleOutcome {
+            authority: Pubkey::new_unique(),
+            is_resolved: true,
+            will_do: true,
+            resolved_at: 42,
+        };
+        let mut data = Vec::new();
+        mock.try_serialize(&mut data).unwrap();
+
+        let key = Pubkey::new_unique();
+        let owner = mock_oracle::ID;
+        let mut lamports = 0;
+        let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+
+        let outcome = OracleOutcome::try_from_account(&account).unwrap();
+        assert_eq!(outcome.authority, mock.authority);
+        assert_eq!(outcome.outcome(), Some(BetType::WillDo));
+    }
+
+    #[test]
+    fn rejects_other_accounts() {
+        let mut data = vec![0u8; 64];
+        let key = Pubkey::new_unique();
+        let owner = mock_oracle::ID;
+        let mut lamports = 0;
+        let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+
+        assert!(OracleOutcome::try_from_account(&account).is_err());
+    }
+}
@@ -0,0 +1,406 @@
+//! Shared payout math and pool transfers.
+//!
+//! Every instruction that pays out of a dare's pool computes its amount here,
+//! so the creator fee, winner rewards and bettor shares always add up the same
+//! way no matter which claim is processed first.
+//!
+//! Products are taken in u128 so `stake * pool` can't overflow, and every
+//! step is checked: a result that doesn't fit returns `MathOverflow` instead
+//! of panicking.
+
+use anchor_lang::prelude::*;
+
+use crate::{BetType, Dare, DareStatus, ErrorCode, FeeSchedule, BPS_DENOMINATOR};
+
+/// `amount * numerator / denominator` with a u128 intermediate
+pub fn mul_div(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    let result = (amount as u128)
+        .checked_mul(numerator as u128)
+        .and_then(|product| product.checked_div(denominator as u128))
+        .ok_or(ErrorCode::MathOverflow)?;
+    u64::try_from(result).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// `amount * bps / 10_000`
+pub fn apply_bps(amount: u64, bps: u16) -> Result<u64> {
+    mul_div(amount, bps as u64, BPS_DENOMINATOR)
+}
+
+/// `a + b`, or `MathOverflow`
+pub fn add(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b).ok_or(ErrorCode::MathOverflow.into())
+}
+
+/// `a - b`, or `MathOverflow`
+pub fn sub(a: u64, b: u64) -> Result<u64> {
+    a.checked_sub(b).ok_or(ErrorCode::MathOverflow.into())
+}
+
+/// Creator's cut of the total pool
+pub fn creator_fee(total_pool: u64, fees: &FeeSchedule) -> Result<u64> {
+    apply_bps(total_pool, fees.creator_fee_bps)
+}
+
+/// Platform's cut of the total pool, collected into the treasury
+pub fn protocol_fee(total_pool: u64, fees: &FeeSchedule) -> Result<u64> {
+    apply_bps(total_pool, fees.protocol_fee_bps)
+}
+
+/// What's left for winners and bettors once the creator and protocol fees are taken
+pub fn distributable_pool(total_pool: u64, fees: &FeeSchedule) -> Result<u64> {
+    let after_creator = sub(total_pool, creator_fee(total_pool, fees)?)?;
+    sub(after_creator, protocol_fee(total_pool, fees)?)
+}
+
+/// Reward for a proof submission ranked 1st, 2nd or 3rd
+pub fn winner_reward(total_pool: u64, fees: &FeeSchedule, rank: u8) -> Result<u64> {
+    let bps = match rank {
+        1 => fees.first_place_bps,
+        2 => fees.second_place_bps,
+        3 => fees.third_place_bps,
+        _ => return err!(ErrorCode::InvalidWinnerRank),
+    };
+    apply_bps(distributable_pool(total_pool, fees)?, bps)
+}
+
+/// Pro-rata share for a "will do" bettor once the dare is completed.
+/// Winner rewards are carved out of the distributable pool first.
+pub fn will_do_payout(
+    stake: u64,
+    will_do_pool: u64,
+    total_pool: u64,
+    fees: &FeeSchedule,
+) -> Result<u64> {
+    if will_do_pool == 0 {
+        return Ok(0);
+    }
+    let available_pool = distributable_pool(total_pool, fees)?;
+    let winner_rewards_total = apply_bps(available_pool, fees.winner_rewards_bps())?;
+    let remaining_pool = sub(available_pool, winner_rewards_total)?;
+    mul_div(stake, remaining_pool, will_do_pool)
+}
+
+/// Pro-rata share for a "won't do" bettor once the dare has failed
+pub fn wont_do_payout(
+    stake: u64,
+    wont_do_pool: u64,
+    total_pool: u64,
+    fees: &FeeSchedule,
+) -> Result<u64> {
+    if wont_do_pool == 0 {
+        return Ok(0);
+    }
+    mul_div(stake, distributable_pool(total_pool, fees)?, wont_do_pool)
+}
+
+/// Splits an early cash-out into `(refund, penalty_to_pool, penalty_to_treasury)`
+pub fn cash_out(stake: u64, fees: &FeeSchedule) -> Result<(u64, u64, u64)> {
+    let penalty = apply_bps(stake, fees.cash_out_penalty_bps)?;
+    let penalty_to_treasury = apply_bps(penalty, fees.cash_out_treasury_bps)?;
+    Ok((
+        sub(stake, penalty)?,
+        sub(penalty, penalty_to_treasury)?,
+        penalty_to_treasury,
+    ))
+}
+
+/// A bettor's share after `emergency_refund`: what is left of the snapshot
+/// pool, split over the stakes that haven't been refunded yet. Paying it and
+/// taking both amounts off keeps every later share at the same rate.
+pub fn emergency_refund(stake: u64, emergency_pool: u64, emergency_stake: u64) -> Result<u64> {
+    mul_div(stake, emergency_pool, emergency_stake)
+}
+
+/// Amount the pool still owes to fee recipients, ranked winners and bettors.
+///
+/// Before settlement the whole `total_pool` is owed, in refund mode every
+/// stake that hasn't been refunded, and after an emergency refund what is left
+/// of its snapshot. After settlement the
+/// bettors' share is taken pro-rata over the winning stake that hasn't been
+/// claimed yet; since each claim rounds down on its own, this aggregate is
+/// never smaller than what the remaining claims will actually pay out.
+pub fn unclaimed_obligations(dare: &Dare) -> Result<u64> {
+    let fees = &dare.fees;
+    let total_pool = dare.total_pool;
+
+    if dare.status == DareStatus::EmergencyRefunding {
+        return Ok(dare.emergency_pool);
+    }
+
+    // Refunds return each position's stake. Cash-out penalties left in the
+    // pool belong to no position and are swept as dust.
+    if dare.is_refund_only() {
+        let total_stake = add(dare.will_do_pool, dare.wont_do_pool)?;
+        return sub(total_stake, dare.refunded_stake);
+    }
+
+    let winning_side = match dare.winning_side() {
+        Some(side) => side,
+        None => return Ok(total_pool),
+    };
+
+    let mut owed = 0u64;
+    if !dare.creator_fee_claimed {
+        owed = add(owed, creator_fee(total_pool, fees)?)?;
+    }
+    if !dare.protocol_fee_collected {
+        owed = add(owed, protocol_fee(total_pool, fees)?)?;
+    }
+
+    let available_pool = distributable_pool(total_pool, fees)?;
+    let (bettor_share, winning_pool) = match winning_side {
+        BetType::WillDo => {
+            let ranks = [
+                (1, dare.first_place_claimed),
+                (2, dare.second_place_claimed),
+                (3, dare.third_place_claimed),
+            ];
+            for (rank, claimed) in ranks {
+                if !claimed {
+                    owed = add(owed, winner_reward(total_pool, fees, rank)?)?;
+                }
+            }
+            let winner_rewards_total = apply_bps(available_pool, fees.winner_rewards_bps())?;
+            (sub(available_pool, winner_rewards_total)?, dare.will_do_pool)
+        }
+        BetType::WontDo => (available_pool, dare.wont_do_pool),
+    };
+
+    if winning_pool > 0 {
+        let unclaimed_stake = sub(winning_pool, dare.winning_stake_claimed)?;
+        owed = add(owed, mul_div(bettor_share, unclaimed_stake, winning_pool)?)?;
+    }
+
+    Ok(owed)
+}
+
+/// What the pool can pay out (`vault::Pool::available`) must always cover
+/// what it still owes. Anything beyond that is rounding dust for `sweep_dust`.
+pub fn check_pool_invariant(available: u64, dare: &Dare) -> Result<()> {
+    require!(
+        available >= unclaimed_obligations(dare)?,
+        ErrorCode::PoolInvariantViolated
+    );
+    Ok(())
+}
+
+/// Moves lamports out of a dare's pool PDA (or the treasury PDA). Both are
+/// created and owned by this program, so they are debited directly rather
+/// than through a system program transfer (which only accepts system-owned
+/// senders).
+pub fn transfer_from_pool(pool: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let pool_balance = sub(pool.lamports(), amount)?;
+    let to_balance = add(to.lamports(), amount)?;
+    **pool.try_borrow_mut_lamports()? = pool_balance;
+    **to.try_borrow_mut_lamports()? = to_balance;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn fee_schedule() -> impl Strategy<Value = FeeSchedule> {
+        (
+            0u16..=5_000,
+            0u16..=5_000,
+            0u16..=10_000,
+            0u16..=10_000,
+            0u16..=10_000,
+            0u16..=10_000,
+            0u16..=10_000,
+            0u16..=10_000,
+        )
+            .prop_filter_map(
+                "winner rewards must not exceed 100%",
+                |(creator, protocol, first, second, third, penalty, treasury, refund)| {
+                    let fees = FeeSchedule {
+                        creator_fee_bps: creator,
+                        protocol_fee_bps: protocol,
+                        first_place_bps: first,
+                        second_place_bps: second,
+                        third_place_bps: third,
+                        cash_out_penalty_bps: penalty,
+                        cash_out_treasury_bps: treasury,
+                        refund_fee_bps: refund,
+                    };
+                    fees.validate().is_ok().then_some(fees)
+                },
+            )
+    }
+
+    /// A dare whose winners have been selected, so "will do" bettors win
+    fn settled_dare(fees: FeeSchedule, will_do_pool: u64, wont_do_pool: u64) -> Dare {
+        Dare {
+            fees,
+            total_pool: will_do_pool.checked_add(wont_do_pool).unwrap(),
+            will_do_pool,
+            wont_do_pool,
+            status: DareStatus::Completed,
+            submission_count: 3,
+            approved_count: 3,
+            first_place_winner: Some(Pubkey::new_unique()),
+            second_place_winner: Some(Pubkey::new_unique()),
+            third_place_winner: Some(Pubkey::new_unique()),
+            outcome: Some(BetType::WillDo),
+            settled_at: 1_000,
+            dispute_deadline: 1_000,
+            ..crate::tests::test_dare()
+        }
+    }
+
+    /// Stakes for one side of the market; the other side's total is added separately
+    fn stakes() -> impl Strategy<Value = Vec<u64>> {
+        prop::collection::vec(1u64..=u64::MAX / 64, 1..32)
+    }
+
+    fn total(stakes: &[u64]) -> u64 {
+        stakes.iter().try_fold(0u64, |acc, s| acc.checked_add(*s)).unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn completed_payouts_never_exceed_pool(
+            fees in fee_schedule(),
+            will_do in stakes(),
+            wont_do_pool in 0u64..=u64::MAX / 2,
+        ) {
+            let will_do_pool = total(&will_do);
+            let total_pool = will_do_pool.checked_add(wont_do_pool).unwrap();
+
+            let mut paid = creator_fee(total_pool, &fees).unwrap() as u128
+                + protocol_fee(total_pool, &fees).unwrap() as u128;
+            for rank in 1..=3 {
+                paid += winner_reward(total_pool, &fees, rank).unwrap() as u128;
+            }
+            for stake in &will_do {
+                paid += will_do_payout(*stake, will_do_pool, total_pool, &fees).unwrap() as u128;
+            }
+
+            prop_assert!(paid <= total_pool as u128);
+        }
+
+        #[test]
+        fn failed_payouts_never_exceed_pool(
+            fees in fee_schedule(),
+            wont_do in stakes(),
+            will_do_pool in 0u64..=u64::MAX / 2,
+        ) {
+            let wont_do_pool = total(&wont_do);
+            let total_pool = wont_do_pool.checked_add(will_do_pool).unwrap();
+
+            let mut paid = creator_fee(total_pool, &fees).unwrap() as u128
+                + protocol_fee(total_pool, &fees).unwrap() as u128;
+            for stake in &wont_do {
+                paid += wont_do_payout(*stake, wont_do_pool, total_pool, &fees).unwrap() as u128;
+            }
+
+            prop_assert!(paid <= total_pool as u128);
+        }
+
+        #[test]
+        fn pool_covers_obligations_through_every_claim(
+            fees in fee_schedule(),
+            will_do in stakes(),
+            wont_do_pool in 0u64..=u64::MAX / 2,
+        ) {
+            let will_do_pool = total(&will_do);
+            let mut dare = settled_dare(fees, will_do_pool, wont_do_pool);
+            let total_pool = dare.total_pool;
+
+            // Pool balance above rent starts out equal to the total pool
+            let mut pool = total_pool;
+            prop_assert!(unclaimed_obligations(&dare).unwrap() <= pool);
+
+            let mut pay = |dare: &mut Dare, amount: u64| {
+                pool = sub(pool, amount).unwrap();
+                dare.total_claimed = add(dare.total_claimed, amount).unwrap();
+                pool
+            };
+
+            let creator = creator_fee(total_pool, &fees).unwrap();
+            dare.creator_fee_claimed = true;
+            let remaining = pay(&mut dare, creator);
+            prop_assert!(remaining >= unclaimed_obligations(&dare).unwrap());
+
+            let protocol = protocol_fee(total_pool, &fees).unwrap();
+            dare.protocol_fee_collected = true;
+            let remaining = pay(&mut dare, protocol);
+            prop_assert!(remaining >= unclaimed_obligations(&dare).unwrap());
+
+            for rank in 1..=3 {
+                let reward = winner_reward(total_pool, &fees, rank).unwrap();
+                match rank {
+                    1 => dare.first_place_claimed = true,
+                    2 => dare.second_place_claimed = true,
+                    _ => dare.third_place_claimed = true,
+                }
+                let remaining = pay(&mut dare, reward);
+                prop_assert!(remaining >= unclaimed_obligations(&dare).unwrap());
+            }
+
+            for stake in &will_do {
+                let winnings = will_do_payout(*stake, will_do_pool, total_pool, &fees).unwrap();
+                dare.winning_stake_claimed = add(dare.winning_stake_claimed, *stake).unwrap();
+                let remaining = pay(&mut dare, winnings);
+                prop_assert!(remaining >= unclaimed_obligations(&dare).unwrap());
+            }
+
+            // Everything is claimed: only rounding dust (at most 1 lamport per division) is left
+            prop_assert_eq!(unclaimed_obligations(&dare).unwrap(), 0);
+            prop_assert_eq!(dare.total_claimed + pool, total_pool);
+            prop_assert!(pool <= will_do.len() as u64 + 6);
+        }
+
+        #[test]
+        fn emergency_refunds_stay_within_snapshot(
+            fees in fee_schedule(),
+            stakes in stakes(),
+            pool_fraction_bps in 0u64..=BPS_DENOMINATOR,
+        ) {
+            let emergency_stake = total(&stakes);
+            let mut dare = settled_dare(fees, emergency_stake, 0);
+            dare.status = DareStatus::EmergencyRefunding;
+            dare.emergency_stake = emergency_stake;
+            // Whatever the pool still held, e.g. after fees or winnings were paid
+            dare.emergency_pool = mul_div(emergency_stake, pool_fraction_bps, BPS_DENOMINATOR).unwrap();
+            let mut pool = dare.emergency_pool;
+
+            for stake in &stakes {
+                let refund = emergency_refund(*stake, dare.emergency_pool, dare.emergency_stake).unwrap();
+                dare.emergency_pool = sub(dare.emergency_pool, refund).unwrap();
+                dare.emergency_stake = sub(dare.emergency_stake, *stake).unwrap();
+                pool = sub(pool, refund).unwrap();
+                prop_assert!(pool >= unclaimed_obligations(&dare).unwrap());
+            }
+
+            // The last bettor takes whatever rounding left behind
+            prop_assert_eq!(pool, 0);
+        }
+
+        #[test]
+        fn cash_out_splits_whole_stake(fees in fee_schedule(), stake in any::<u64>()) {
+            let (refund, to_pool, to_treasury) = cash_out(stake, &fees).unwrap();
+
+            prop_assert_eq!(refund as u128 + to_pool as u128 + to_treasury as u128, stake as u128);
+        }
+    }
+
+    #[test]
+    fn invalid_rank_has_no_reward() {
+        let fees = FeeSchedule {
+            creator_fee_bps: 200,
+            protocol_fee_bps: 100,
+            first_place_bps: 3_000,
+            second_place_bps: 1_500,
+            third_place_bps: 500,
+            cash_out_penalty_bps: 1_000,
+            cash_out_treasury_bps: 5_000,
+            refund_fee_bps: 0,
+        };
+
+        assert!(winner_reward(1_000, &fees, 0).is_err());
+        assert!(winner_reward(1_000, &fees, 4).is_err());
+    }
+}
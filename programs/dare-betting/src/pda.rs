@@ -0,0 +1,191 @@
+//! Client-side helpers for deriving the program's PDAs.
+//!
+//! These mirror the `seeds` constraints in the instruction account structs and
+//! are the single reference for how each account is addressed.
+
+use anchor_lang::prelude::*;
+
+/// `[b"config"]` - the global `ProgramConfig` singleton
+pub fn find_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config"], &crate::ID)
+}
+
+/// `[b"treasury"]` - collects protocol fees and cash-out penalties
+pub fn find_treasury_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"treasury"], &crate::ID)
+}
+
+/// `[b"allowed_mint", mint]` - allowlist entry for a token dares can use
+pub fn find_allowed_mint_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"allowed_mint", mint.as_ref()], &crate::ID)
+}
+
+/// `[b"pending_action", config, action_id.to_le_bytes()]` - a timelocked admin action
+pub fn find_pending_action_address(action_id: u64) -> (Pubkey, u8) {
+    let (config, _) = find_config_address();
+    Pubkey::find_program_address(
+        &[b"pending_action", config.as_ref(), &action_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// `[b"dare", creator, title]`
+pub fn find_dare_address(creator: &Pubkey, title: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"dare", creator.as_ref(), title.as_bytes()], &crate::ID)
+}
+
+/// `[b"pool", dare]` - holds a SOL dare's stakes and owns a token dare's vault
+pub fn find_pool_address(dare: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pool", dare.as_ref()], &crate::ID)
+}
+
+/// `[b"vault", dare]` - token account holding a token dare's stakes, owned by the pool PDA
+pub fn find_vault_address(dare: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", dare.as_ref()], &crate::ID)
+}
+
+/// `[b"bet", dare, bettor]`
+pub fn find_bet_address(dare: &Pubkey, bettor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"bet", dare.as_ref(), bettor.as_ref()], &crate::ID)
+}
+
+/// `[b"proof", dare, submission_index.to_le_bytes()]`
+///
+/// Submissions are numbered per dare starting at 0. The next index is the
+/// dare's current `submission_count`, so a client submitting proof derives
+/// the address from the freshly fetched `Dare` account.
+pub fn find_proof_submission_address(dare: &Pubkey, submission_index: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"proof", dare.as_ref(), &submission_index.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// `[b"dispute", dare]`, at most one open dispute per dare
+pub fn find_dispute_address(dare: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"dispute", dare.as_ref()], &crate::ID)
+}
+
+/// `[b"committee", committee_id.to_le_bytes()]`
+pub fn find_committee_address(committee_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"committee", &committee_id.to_le_bytes()], &crate::ID)
+}
+
+/// `[b"committee_vote", dare, member]`
+pub fn find_committee_vote_address(dare: &Pubkey, member: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"committee_vote", dare.as_ref(), member.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// `[b"vote", dare, voter]`, one community vote per bettor
+pub fn find_vote_address(dare: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vote", dare.as_ref(), voter.as_ref()], &crate::ID)
+}
+
+/// `[b"like", proof_submission, liker]`, one like per wallet
+pub fn find_like_address(proof_submission: &Pubkey, liker: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"like", proof_submission.as_ref(), liker.as_ref()],
+        &crate::ID,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn proof_submissions_are_numbered_per_dare() {
+        let dare = Pubkey::new_unique();
+        let (first, bump) = find_proof_submission_address(&dare, 0);
+
+        // The seeds submit_proof, claim_winner_reward and the like instructions check
+        let seeds: &[&[u8]] = &[b"proof", dare.as_ref(), &[0, 0, 0, 0], &[bump]];
+        assert_eq!(
+            Pubkey::create_program_address(seeds, &crate::ID).unwrap(),
+            first
+        );
+
+        assert_eq!(find_proof_submission_address(&dare, 0).0, first);
+        assert_ne!(find_proof_submission_address(&dare, 1).0, first);
+        assert_ne!(
+            find_proof_submission_address(&Pubkey::new_unique(), 0).0,
+            first
+        );
+    }
+
+    #[test]
+    fn submission_index_is_little_endian() {
+        let dare = Pubkey::new_unique();
+        let (address, bump) = find_proof_submission_address(&dare, 256);
+        let seeds: &[&[u8]] = &[b"proof", dare.as_ref(), &[0, 1, 0, 0], &[bump]];
+        assert_eq!(
+            Pubkey::create_program_address(seeds, &crate::ID).unwrap(),
+            address
+        );
+    }
+
+    #[test]
+    fn pending_actions_are_scoped_to_the_config() {
+        let (config, _) = find_config_address();
+        let (address, bump) = find_pending_action_address(7);
+        let seeds: &[&[u8]] = &[
+            b"pending_action",
+            config.as_ref(),
+            &7u64.to_le_bytes(),
+            &[bump],
+        ];
+        assert_eq!(
+            Pubkey::create_program_address(seeds, &crate::ID).unwrap(),
+            address
+        );
+        assert_ne!(find_pending_action_address(8).0, address);
+    }
+
+    #[test]
+    fn dares_are_keyed_by_creator_and_title() {
+        let creator = Pubkey::new_unique();
+        let (dare, _) = find_dare_address(&creator, "Eat a lemon");
+        assert_eq!(find_dare_address(&creator, "Eat a lemon").0, dare);
+        assert_ne!(find_dare_address(&creator, "Eat two lemons").0, dare);
+        assert_ne!(
+            find_dare_address(&Pubkey::new_unique(), "Eat a lemon").0,
+            dare
+        );
+    }
+
+    #[test]
+    fn accounts_of_one_dare_do_not_collide() {
+        let dare = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let (proof_submission, _) = find_proof_submission_address(&dare, 0);
+        let addresses = [
+            find_pool_address(&dare).0,
+            find_vault_address(&dare).0,
+            find_dispute_address(&dare).0,
+            find_bet_address(&dare, &user).0,
+            find_vote_address(&dare, &user).0,
+            find_committee_vote_address(&dare, &user).0,
+            find_like_address(&proof_submission, &user).0,
+            proof_submission,
+        ];
+        assert_eq!(
+            addresses.iter().collect::<HashSet<_>>().len(),
+            addresses.len()
+        );
+    }
+
+    #[test]
+    fn singletons_are_off_curve() {
+        for (address, _) in [
+            find_config_address(),
+            find_treasury_address(),
+            find_committee_address(1),
+        ] {
+            assert!(!address.is_on_curve());
+        }
+    }
+}
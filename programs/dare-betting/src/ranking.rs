@@ -0,0 +1,106 @@
+//! Ordering of approved submissions for `finalize_rankings`.
+
+use std::cmp::Ordering;
+
+use crate::ProofSubmission;
+
+/// Most likes first. Equal likes go to the earlier submission, and submissions
+/// made in the same second to the lower index, so the order is total and
+/// doesn't depend on the order the accounts were passed in.
+pub fn compare(a: &ProofSubmission, b: &ProofSubmission) -> Ordering {
+    b.likes_count
+        .cmp(&a.likes_count)
+        .then(a.submission_timestamp.cmp(&b.submission_timestamp))
+        .then(a.submission_index.cmp(&b.submission_index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+
+    fn submission(
+        submission_index: u32,
+        likes_count: u32,
+        submission_timestamp: i64,
+    ) -> ProofSubmission {
+        ProofSubmission {
+            dare: Pubkey::default(),
+            submitter: Pubkey::new_unique(),
+            submission_index,
+            proof_hash: String::new(),
+            proof_description: String::new(),
+            submission_timestamp,
+            is_approved: true,
+            is_rejected: false,
+            rejection_reason: 0,
+            approved_by: Pubkey::default(),
+            approval_timestamp: 0,
+            likes_count,
+            is_winner: false,
+            winner_rank: 0,
+            reward_claimed: false,
+            bump: 0,
+        }
+    }
+
+    fn ranked(mut submissions: Vec<ProofSubmission>) -> Vec<u32> {
+        submissions.sort_by(compare);
+        submissions.iter().map(|s| s.submission_index).collect()
+    }
+
+    #[test]
+    fn more_likes_rank_first() {
+        assert_eq!(
+            compare(&submission(0, 5, 100), &submission(1, 3, 50)),
+            Ordering::Less
+        );
+        assert_eq!(
+            ranked(vec![
+                submission(0, 1, 0),
+                submission(1, 7, 0),
+                submission(2, 4, 0)
+            ]),
+            [1, 2, 0]
+        );
+    }
+
+    #[test]
+    fn equal_likes_go_to_the_earlier_submission() {
+        assert_eq!(
+            compare(&submission(3, 5, 100), &submission(1, 5, 200)),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn same_second_goes_to_the_lower_index() {
+        assert_eq!(
+            compare(&submission(1, 5, 100), &submission(2, 5, 100)),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare(&submission(2, 5, 100), &submission(2, 5, 100)),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn ranking_ignores_input_order() {
+        let submissions = || {
+            vec![
+                submission(0, 2, 10),
+                submission(1, 9, 30),
+                submission(2, 9, 20),
+                submission(3, 2, 10),
+                submission(4, 0, 0),
+            ]
+        };
+        let expected = [2, 1, 0, 3, 4];
+        assert_eq!(ranked(submissions()), expected);
+
+        let mut reversed = submissions();
+        reversed.reverse();
+        assert_eq!(ranked(reversed), expected);
+    }
+}
@@ -0,0 +1,185 @@
+//! Moving a dare's stakes in and out of its pool.
+//!
+//! SOL dares keep lamports directly in the pool PDA `[b"pool", dare]`. Token
+//! dares (`Dare::mint` is set) keep tokens in a vault token account at
+//! `[b"vault", dare]` whose authority is the pool PDA, and move them with
+//! `transfer_checked`, which works for both SPL Token and Token-2022 mints.
+//! Instructions take the token accounts as optional accounts and leave them
+//! out for SOL dares.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{payout, Dare, ErrorCode};
+
+struct TokenPool<'a, 'info> {
+    mint: &'a InterfaceAccount<'info, Mint>,
+    vault: &'a InterfaceAccount<'info, TokenAccount>,
+    token_program: &'a Interface<'info, TokenInterface>,
+}
+
+pub struct Pool<'a, 'info> {
+    account: AccountInfo<'info>,
+    dare: Pubkey,
+    bump: u8,
+    tokens: Option<TokenPool<'a, 'info>>,
+}
+
+impl<'a, 'info> Pool<'a, 'info> {
+    /// Checks that the token accounts match the dare: all present with the
+    /// dare's mint and a vault owned by the pool for token dares, and ignored
+    /// for SOL dares.
+    pub fn new(
+        dare: &Account<'info, Dare>,
+        account: &AccountInfo<'info>,
+        bump: u8,
+        mint: &'a Option<InterfaceAccount<'info, Mint>>,
+        vault: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+        token_program: &'a Option<Interface<'info, TokenInterface>>,
+    ) -> Result<Self> {
+        let tokens = match dare.mint {
+            None => None,
+            Some(dare_mint) => {
+                let (Some(mint), Some(vault), Some(token_program)) = (mint, vault, token_program)
+                else {
+                    return err!(ErrorCode::MissingTokenAccounts);
+                };
+                require_keys_eq!(mint.key(), dare_mint, ErrorCode::MintMismatch);
+                require_keys_eq!(vault.mint, dare_mint, ErrorCode::MintMismatch);
+                require_keys_eq!(vault.owner, account.key(), ErrorCode::InvalidVault);
+                require_keys_eq!(
+                    *mint.to_account_info().owner,
+                    token_program.key(),
+                    ErrorCode::MintMismatch
+                );
+                Some(TokenPool { mint, vault, token_program })
+            }
+        };
+
+        Ok(Self {
+            account: account.clone(),
+            dare: dare.key(),
+            bump,
+            tokens,
+        })
+    }
+
+    /// Moves a stake from `from` into the pool. Token dares pull from
+    /// `from_tokens`, which `from` must be able to sign for.
+    pub fn deposit(
+        &self,
+        from: &AccountInfo<'info>,
+        from_tokens: Option<&InterfaceAccount<'info, TokenAccount>>,
+        system_program: &Program<'info, System>,
+        amount: u64,
+    ) -> Result<()> {
+        match &self.tokens {
+            None => system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: from.clone(),
+                        to: self.account.clone(),
+                    },
+                ),
+                amount,
+            ),
+            Some(tokens) => {
+                let from_tokens = from_tokens.ok_or(ErrorCode::MissingTokenAccounts)?;
+                token_interface::transfer_checked(
+                    CpiContext::new(
+                        tokens.token_program.to_account_info(),
+                        TransferChecked {
+                            from: from_tokens.to_account_info(),
+                            mint: tokens.mint.to_account_info(),
+                            to: tokens.vault.to_account_info(),
+                            authority: from.clone(),
+                        },
+                    ),
+                    amount,
+                    tokens.mint.decimals,
+                )
+            }
+        }
+    }
+
+    /// Pays `amount` out of the pool to `recipient`. Token dares pay into
+    /// `recipient_tokens`, which must be `recipient`'s account for the dare's mint.
+    pub fn pay(
+        &self,
+        recipient: &AccountInfo<'info>,
+        recipient_tokens: Option<&InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<()> {
+        let Some(tokens) = &self.tokens else {
+            return payout::transfer_from_pool(&self.account, recipient, amount);
+        };
+        let recipient_tokens = recipient_tokens.ok_or(ErrorCode::MissingTokenAccounts)?;
+        require_keys_eq!(recipient_tokens.owner, recipient.key(), ErrorCode::InvalidTokenAccount);
+        require_keys_eq!(recipient_tokens.mint, tokens.mint.key(), ErrorCode::MintMismatch);
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let bump = [self.bump];
+        let seeds: &[&[u8]] = &[b"pool", self.dare.as_ref(), &bump];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                tokens.token_program.to_account_info(),
+                TransferChecked {
+                    from: tokens.vault.to_account_info(),
+                    mint: tokens.mint.to_account_info(),
+                    to: recipient_tokens.to_account_info(),
+                    authority: self.account.clone(),
+                },
+                &[seeds],
+            ),
+            amount,
+            tokens.mint.decimals,
+        )
+    }
+
+    /// What the pool can pay out: lamports above the pool's rent-exempt
+    /// minimum, or the vault's whole token balance.
+    pub fn available(&self) -> Result<u64> {
+        match &self.tokens {
+            None => {
+                let rent_exempt_minimum = Rent::get()?.minimum_balance(self.account.data_len());
+                Ok(self.account.lamports().saturating_sub(rent_exempt_minimum))
+            }
+            Some(tokens) => {
+                // Re-read the vault: earlier transfers in this instruction changed it
+                let vault = tokens.vault.to_account_info();
+                let data = vault.try_borrow_data()?;
+                Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+            }
+        }
+    }
+
+    /// See `payout::check_pool_invariant`
+    pub fn check_invariant(&self, dare: &Dare) -> Result<()> {
+        payout::check_pool_invariant(self.available()?, dare)
+    }
+
+    /// Closes an empty vault, returning its rent to `destination`. A no-op for SOL dares.
+    pub fn close_vault(&self, destination: &AccountInfo<'info>) -> Result<()> {
+        let Some(tokens) = &self.tokens else {
+            return Ok(());
+        };
+
+        let bump = [self.bump];
+        let seeds: &[&[u8]] = &[b"pool", self.dare.as_ref(), &bump];
+        token_interface::close_account(CpiContext::new_with_signer(
+            tokens.token_program.to_account_info(),
+            CloseAccount {
+                account: tokens.vault.to_account_info(),
+                destination: destination.clone(),
+                authority: self.account.clone(),
+            },
+            &[seeds],
+        ))
+    }
+}
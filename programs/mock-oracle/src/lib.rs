@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+declare_id!("MHbc52LGGzxSrXHxPGraZvw2sagNF9tw8qHKcWwKCSZ");
+
+/// Test stand-in for a real data feed. Whoever creates an outcome account can
+/// later report the result, which `dare_betting::resolve_from_oracle` reads.
+#[program]
+pub mod mock_oracle {
+    use super::*;
+
+    pub fn initialize_outcome(ctx: Context<InitializeOutcome>) -> Result<()> {
+        let outcome = &mut ctx.accounts.outcome;
+        outcome.authority = ctx.accounts.authority.key();
+        outcome.is_resolved = false;
+        outcome.will_do = false;
+        outcome.resolved_at = 0;
+
+        Ok(())
+    }
+
+    pub fn set_outcome(ctx: Context<SetOutcome>, will_do: bool) -> Result<()> {
+        let outcome = &mut ctx.accounts.outcome;
+        outcome.is_resolved = true;
+        outcome.will_do = will_do;
+        outcome.resolved_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeOutcome<'info> {
+    #[account(init, payer = authority, space = OracleOutcome::LEN)]
+    pub outcome: Account<'info, OracleOutcome>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetOutcome<'info> {
+    #[account(mut, has_one = authority)]
+    pub outcome: Account<'info, OracleOutcome>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Same layout as `dare_betting::OracleOutcome`
+#[account]
+pub struct OracleOutcome {
+    pub authority: Pubkey,
+    pub is_resolved: bool,
+    pub will_do: bool,
+    pub resolved_at: i64,
+}
+
+impl OracleOutcome {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        1 + // is_resolved
+        1 + // will_do
+        8; // resolved_at
+}
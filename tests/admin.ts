@@ -0,0 +1,229 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { PublicKey, Keypair, SystemProgram, Transaction, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  airdrop,
+  betAddress,
+  configAddress,
+  createDare,
+  ensureConfig,
+  pendingActionAddress,
+  placeBet,
+  poolAddress,
+  sleepUntil,
+  treasuryAddress,
+  WITHDRAWAL_TIMELOCK_SECS,
+} from "./helpers";
+
+describe("admin", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const config = configAddress(program);
+  const treasury = treasuryAddress(program);
+
+  let creator: Keypair;
+  let bettors: Keypair[];
+
+  before(async () => {
+    creator = Keypair.generate();
+    bettors = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+
+    await airdrop(provider, creator, ...bettors);
+    await ensureConfig(program, provider);
+  });
+
+  async function setWithdrawalTimelock(secs: number) {
+    await program.methods
+      .setWithdrawalTimelock(new anchor.BN(secs))
+      .accounts({ config, admin: provider.wallet.publicKey })
+      .rpc();
+  }
+
+  async function queueWithdrawal(recipient: PublicKey, amount: number) {
+    const actionId = new anchor.BN(Date.now());
+    const pendingAction = pendingActionAddress(program, actionId);
+    await program.methods
+      .queueTreasuryWithdrawal(actionId, null, recipient, new anchor.BN(amount))
+      .accounts({
+        config,
+        pendingAction,
+        admin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return pendingAction;
+  }
+
+  async function executeWithdrawal(pendingAction: PublicKey, recipient: PublicKey, executor: Keypair) {
+    await program.methods
+      .executePendingAction()
+      .accounts({
+        config,
+        pendingAction,
+        treasury,
+        recipient,
+        mint: null,
+        treasuryTokens: null,
+        tokenProgram: null,
+        admin: provider.wallet.publicKey,
+        executor: executor.publicKey,
+      })
+      .signers([executor])
+      .rpc();
+  }
+
+  async function claimRefund(dare: PublicKey, bettor: Keypair) {
+    const before = await provider.connection.getBalance(bettor.publicKey);
+    await program.methods
+      .claimRefund()
+      .accounts({
+        dare,
+        bet: betAddress(program, dare, bettor.publicKey),
+        poolAccount: poolAddress(program, dare),
+        config,
+        treasury,
+        bettor: bettor.publicKey,
+      })
+      .signers([bettor])
+      .rpc();
+    return (await provider.connection.getBalance(bettor.publicKey)) - before;
+  }
+
+  it("Only lets the withdrawal timelock be lengthened", async () => {
+    try {
+      await setWithdrawalTimelock(WITHDRAWAL_TIMELOCK_SECS - 1);
+      expect.fail("shortened the withdrawal timelock");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidTimelock");
+    }
+
+    // Keep the suites' short timelock
+    await setWithdrawalTimelock(WITHDRAWAL_TIMELOCK_SECS);
+    const { withdrawalTimelockSecs } = await program.account.programConfig.fetch(config) as any;
+    expect(withdrawalTimelockSecs.toNumber()).to.equal(WITHDRAWAL_TIMELOCK_SECS);
+  });
+
+  it("Holds queued treasury withdrawals until the timelock has passed", async () => {
+    const pendingAction = await queueWithdrawal(creator.publicKey, 1);
+
+    try {
+      await executeWithdrawal(pendingAction, creator.publicKey, bettors[0]);
+      expect.fail("executed a withdrawal before its timelock");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("TimelockNotElapsed");
+    }
+
+    await program.methods
+      .cancelPendingAction()
+      .accounts({ config, pendingAction, admin: provider.wallet.publicKey })
+      .rpc();
+    expect(await program.account.pendingAction.fetchNullable(pendingAction)).to.be.null;
+  });
+
+  it("Pays out a queued treasury withdrawal once the timelock has passed", async () => {
+    // Revenue for the treasury to pay out
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: treasury,
+          lamports: LAMPORTS_PER_SOL,
+        })
+      )
+    );
+
+    const recipient = Keypair.generate().publicKey;
+    const amount = LAMPORTS_PER_SOL / 2;
+    const pendingAction = await queueWithdrawal(recipient, amount);
+    const { executableAt } = await program.account.pendingAction.fetch(pendingAction) as any;
+    await sleepUntil(executableAt);
+
+    try {
+      await executeWithdrawal(pendingAction, creator.publicKey, bettors[0]);
+      expect.fail("paid a withdrawal to someone else");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("PendingActionMismatch");
+    }
+
+    // Permissionless once the timelock has passed
+    const treasuryBefore = await provider.connection.getBalance(treasury);
+    await executeWithdrawal(pendingAction, recipient, bettors[0]);
+    const treasuryAfter = await provider.connection.getBalance(treasury);
+
+    expect(await provider.connection.getBalance(recipient)).to.equal(amount);
+    expect(treasuryBefore - treasuryAfter).to.equal(amount);
+    expect(await program.account.pendingAction.fetchNullable(pendingAction)).to.be.null;
+  });
+
+  it("Refunds a halted dare pro-rata to the stakes still in it", async () => {
+    const dare = await createDare(program, creator, "Halted Dare");
+    await placeBet(program, dare, bettors[0], LAMPORTS_PER_SOL, "willDo");
+    await placeBet(program, dare, bettors[1], 3 * LAMPORTS_PER_SOL, "wontDo");
+
+    // A cash-out leaves part of its penalty in the pool, on top of the stakes
+    await placeBet(program, dare, bettors[2], LAMPORTS_PER_SOL, "wontDo");
+    await program.methods
+      .cashOutEarly()
+      .accounts({
+        dare,
+        bet: betAddress(program, dare, bettors[2].publicKey),
+        poolAccount: poolAddress(program, dare),
+        config,
+        treasury,
+        bettor: bettors[2].publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bettors[2]])
+      .rpc();
+
+    const emergencyRefund = (admin: Keypair | null) =>
+      program.methods
+        .emergencyRefund()
+        .accounts({
+          dare,
+          poolAccount: poolAddress(program, dare),
+          config,
+          admin: admin ? admin.publicKey : provider.wallet.publicKey,
+        })
+        .signers(admin ? [admin] : [])
+        .rpc();
+
+    try {
+      await emergencyRefund(creator);
+      expect.fail("the creator halted the dare");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ConstraintHasOne");
+    }
+
+    await emergencyRefund(null);
+    const dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.status).to.deep.equal({ emergencyRefunding: {} });
+    expect(dareAccount.emergencyStake.toNumber()).to.equal(4 * LAMPORTS_PER_SOL);
+
+    try {
+      await placeBet(program, dare, bettors[2], LAMPORTS_PER_SOL, "willDo");
+      expect.fail("bet on a halted dare");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidDareStatus");
+    }
+
+    // Each stake gets its share of everything the pool held, with no fee
+    const pool = dareAccount.emergencyPool.toNumber();
+    expect(pool).to.be.greaterThan(4 * LAMPORTS_PER_SOL);
+    const first = await claimRefund(dare, bettors[0]);
+    expect(first).to.equal(Math.floor(pool / 4)); // 1 of the 4 SOL at stake
+    expect(await claimRefund(dare, bettors[1])).to.equal(pool - first);
+    expect((await program.account.dare.fetch(dare) as any).emergencyPool.toNumber()).to.equal(0);
+
+    try {
+      await claimRefund(dare, bettors[0]);
+      expect.fail("refunded twice");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AlreadyClaimed");
+    }
+  });
+});
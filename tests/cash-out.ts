@@ -0,0 +1,101 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  airdrop,
+  betAddress,
+  configAddress,
+  createDare,
+  ensureConfig,
+  placeBet,
+  poolAddress,
+  treasuryAddress,
+} from "./helpers";
+
+describe("cash-out", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const config = configAddress(program);
+  const treasury = treasuryAddress(program);
+
+  let creator: Keypair;
+  let bettor: Keypair;
+
+  before(async () => {
+    creator = Keypair.generate();
+    bettor = Keypair.generate();
+
+    await airdrop(provider, creator, bettor);
+    await ensureConfig(program, provider);
+  });
+
+  async function cashOut(dare: PublicKey, cashingOut: Keypair) {
+    await program.methods
+      .cashOutEarly()
+      .accounts({
+        dare,
+        bet: betAddress(program, dare, cashingOut.publicKey),
+        poolAccount: poolAddress(program, dare),
+        config,
+        treasury,
+        bettor: cashingOut.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([cashingOut])
+      .rpc();
+  }
+
+  it("Cashes out early, leaving the pool's share of the penalty behind", async () => {
+    const dare = await createDare(program, creator, "Cash Out Dare");
+    const bet = betAddress(program, dare, bettor.publicKey);
+    const stake = LAMPORTS_PER_SOL;
+
+    // A top-up keeps the position's first-deposit time
+    await placeBet(program, dare, bettor, stake / 2, "willDo");
+    const { betTimestamp } = await program.account.bet.fetch(bet) as any;
+    await placeBet(program, dare, bettor, stake / 2, "wontDo");
+    expect((await program.account.bet.fetch(bet) as any).betTimestamp.toString()).to.equal(
+      betTimestamp.toString()
+    );
+
+    const { fees } = await program.account.dare.fetch(dare) as any;
+    const penalty = (stake * fees.cashOutPenaltyBps) / 10000;
+    const penaltyToTreasury = (penalty * fees.cashOutTreasuryBps) / 10000;
+
+    const treasuryBefore = await provider.connection.getBalance(treasury);
+    await cashOut(dare, bettor);
+    const treasuryAfter = await provider.connection.getBalance(treasury);
+
+    // The position is closed out, not claimed: the bettor may bet again
+    const betAccount = await program.account.bet.fetch(bet) as any;
+    expect(betAccount.isClaimed).to.be.false;
+    expect(betAccount.willDoAmount.toNumber()).to.equal(0);
+    expect(betAccount.wontDoAmount.toNumber()).to.equal(0);
+
+    const dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.willDoPool.toNumber()).to.equal(0);
+    expect(dareAccount.wontDoPool.toNumber()).to.equal(0);
+    expect(dareAccount.totalPool.toNumber()).to.equal(penalty - penaltyToTreasury);
+    expect(treasuryAfter - treasuryBefore).to.equal(penaltyToTreasury);
+
+    await placeBet(program, dare, bettor, stake, "willDo");
+    expect((await program.account.bet.fetch(bet) as any).willDoAmount.toNumber()).to.equal(stake);
+  });
+
+  it("Rejects cashing out an empty position", async () => {
+    const dare = await createDare(program, creator, "Empty Cash Out Dare");
+    await placeBet(program, dare, bettor, LAMPORTS_PER_SOL, "willDo");
+    await cashOut(dare, bettor);
+
+    try {
+      await cashOut(dare, bettor);
+      expect.fail("cashed out the same position twice");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NoPayout");
+    }
+  });
+});
@@ -0,0 +1,138 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  airdrop,
+  committeeAddress,
+  configAddress,
+  createDare,
+  ensureConfig,
+  placeBet,
+  resolveDare,
+  sleep,
+} from "./helpers";
+
+describe("committees", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const config = configAddress(program);
+
+  let creator: Keypair;
+  let bettor: Keypair;
+  let members: Keypair[];
+  let committee: PublicKey;
+
+  async function createCommittee(committeeMembers: PublicKey[], threshold: number) {
+    const committeeId = new anchor.BN(Date.now());
+    const address = committeeAddress(program, committeeId);
+    await program.methods
+      .createCommittee(committeeId, committeeMembers, threshold)
+      .accounts({
+        config,
+        committee: address,
+        admin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return address;
+  }
+
+  async function voteResolution(dare: PublicKey, member: Keypair, outcome: "willDo" | "wontDo") {
+    const [vote] = PublicKey.findProgramAddressSync(
+      [Buffer.from("committee_vote"), dare.toBuffer(), member.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .voteResolution({ [outcome]: {} })
+      .accounts({
+        dare,
+        committee,
+        vote,
+        config,
+        member: member.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([member])
+      .rpc();
+  }
+
+  before(async () => {
+    creator = Keypair.generate();
+    bettor = Keypair.generate();
+    members = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+
+    await airdrop(provider, creator, bettor, ...members);
+    await ensureConfig(program, provider);
+
+    committee = await createCommittee(members.map((m) => m.publicKey), 2);
+  });
+
+  it("Requires a majority threshold", async () => {
+    try {
+      await createCommittee(members.slice(0, 2).map((m) => m.publicKey), 1);
+      expect.fail("created a committee one member of two could decide for");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidCommittee");
+    }
+  });
+
+  it("Only creates committee dares for a committee that exists", async () => {
+    try {
+      await createDare(program, creator, "Committee-less Dare", {
+        resolutionMode: { committee: { committee } },
+      });
+      expect.fail("created a committee dare without its committee");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("CommitteeMismatch");
+    }
+  });
+
+  it("Resolves once a majority of members agree", async () => {
+    const dare = await createDare(program, creator, "Committee Dare", {
+      bettingSecs: 4,
+      resolutionMode: { committee: { committee } },
+      committee,
+    });
+    await placeBet(program, dare, bettor, LAMPORTS_PER_SOL, "willDo");
+    await placeBet(program, dare, creator, LAMPORTS_PER_SOL, "wontDo");
+    await sleep(5000);
+
+    try {
+      await resolveDare(program, provider, dare, "willDo");
+      expect.fail("the admin resolved a committee dare");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ResolvedByCommittee");
+    }
+
+    try {
+      await voteResolution(dare, bettor, "wontDo");
+      expect.fail("an outsider voted");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotCommitteeMember");
+    }
+
+    await voteResolution(dare, members[0], "willDo");
+    let dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.committeeWillDoVotes).to.equal(1);
+    expect(dareAccount.status).to.deep.equal({ awaitingResolution: {} });
+
+    try {
+      await voteResolution(dare, members[0], "willDo");
+      expect.fail("a member voted twice");
+    } catch (err: any) {
+      expect(err.toString()).to.include("already in use");
+    }
+
+    await voteResolution(dare, members[1], "wontDo");
+    await voteResolution(dare, members[2], "willDo");
+    dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.committeeWillDoVotes).to.equal(2);
+    expect(dareAccount.committeeWontDoVotes).to.equal(1);
+    expect(dareAccount.status).to.deep.equal({ completed: {} });
+    expect(dareAccount.outcome).to.deep.equal({ willDo: {} });
+  });
+});
@@ -0,0 +1,127 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  airdrop,
+  betAddress,
+  configAddress,
+  createDare,
+  ensureConfig,
+  placeBet,
+  resolveDare,
+  RESOLUTION_WINDOW_SECS,
+  sleep,
+} from "./helpers";
+
+describe("community votes", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const config = configAddress(program);
+
+  let creator: Keypair;
+  let whale: Keypair;
+  let bettors: Keypair[];
+
+  before(async () => {
+    creator = Keypair.generate();
+    whale = Keypair.generate();
+    bettors = [Keypair.generate(), Keypair.generate()];
+
+    await airdrop(provider, creator, whale, ...bettors);
+    await ensureConfig(program, provider);
+  });
+
+  async function castVote(dare: PublicKey, voter: Keypair, outcome: "willDo" | "wontDo") {
+    const [vote] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vote"), dare.toBuffer(), voter.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .castVote({ [outcome]: {} })
+      .accounts({
+        dare,
+        bet: betAddress(program, dare, voter.publicKey),
+        vote,
+        voter: voter.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  async function finalizeCommunityVote(dare: PublicKey) {
+    await program.methods.finalizeCommunityVote().accounts({ dare, config }).rpc();
+  }
+
+  it("Weighs votes by stake and takes one vote per bettor", async () => {
+    const dare = await createDare(program, creator, "Community Dare", {
+      bettingSecs: 4,
+      resolutionMode: { community: {} },
+    });
+    await placeBet(program, dare, whale, 3 * LAMPORTS_PER_SOL, "willDo");
+    await placeBet(program, dare, bettors[0], LAMPORTS_PER_SOL, "wontDo");
+    await placeBet(program, dare, bettors[1], LAMPORTS_PER_SOL, "wontDo");
+    await sleep(5000);
+
+    try {
+      await resolveDare(program, provider, dare, "willDo");
+      expect.fail("the admin resolved a community dare");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ResolvedByCommunity");
+    }
+
+    // Two voters against one, but the one has more at stake
+    await castVote(dare, whale, "wontDo");
+    await castVote(dare, bettors[0], "willDo");
+    await castVote(dare, bettors[1], "willDo");
+
+    try {
+      await castVote(dare, whale, "willDo");
+      expect.fail("a bettor voted twice");
+    } catch (err: any) {
+      expect(err.toString()).to.include("already in use");
+    }
+
+    let dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.communityWillDoWeight.toNumber()).to.equal(2 * LAMPORTS_PER_SOL);
+    expect(dareAccount.communityWontDoWeight.toNumber()).to.equal(3 * LAMPORTS_PER_SOL);
+
+    try {
+      await finalizeCommunityVote(dare);
+      expect.fail("finalized while voting was open");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("VotingStillOpen");
+    }
+
+    await sleep(RESOLUTION_WINDOW_SECS * 1000);
+    await finalizeCommunityVote(dare);
+
+    dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.outcome).to.deep.equal({ wontDo: {} });
+    expect(dareAccount.status).to.deep.equal({ failed: {} });
+  });
+
+  it("Resolves a tied vote as failed", async () => {
+    const dare = await createDare(program, creator, "Tied Community Dare", {
+      bettingSecs: 4,
+      resolutionMode: { community: {} },
+    });
+    await placeBet(program, dare, bettors[0], LAMPORTS_PER_SOL, "willDo");
+    await placeBet(program, dare, bettors[1], LAMPORTS_PER_SOL, "wontDo");
+    await sleep(5000);
+
+    await castVote(dare, bettors[0], "willDo");
+    await castVote(dare, bettors[1], "wontDo");
+
+    await sleep(RESOLUTION_WINDOW_SECS * 1000);
+    await finalizeCommunityVote(dare);
+
+    const dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.outcome).to.deep.equal({ wontDo: {} });
+    expect(dareAccount.status).to.deep.equal({ failed: {} });
+  });
+});
@@ -0,0 +1,126 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import {
+  airdrop,
+  configAddress,
+  ensureConfig,
+  FEES,
+  RESOLUTION_WINDOW_SECS,
+  treasuryAddress,
+} from "./helpers";
+
+describe("config", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const config = configAddress(program);
+  const wallet = provider.wallet.publicKey;
+
+  let nominee: Keypair;
+  let outsider: Keypair;
+
+  before(async () => {
+    nominee = Keypair.generate();
+    outsider = Keypair.generate();
+
+    await airdrop(provider, nominee, outsider);
+    await ensureConfig(program, provider);
+  });
+
+  async function proposeAdmin(admin: Keypair | null, newAdmin: PublicKey) {
+    await program.methods
+      .proposeAdmin(newAdmin)
+      .accounts({ config, admin: admin ? admin.publicKey : wallet })
+      .signers(admin ? [admin] : [])
+      .rpc();
+  }
+
+  async function acceptAdmin(newAdmin: Keypair | null) {
+    await program.methods
+      .acceptAdmin()
+      .accounts({ config, newAdmin: newAdmin ? newAdmin.publicKey : wallet })
+      .signers(newAdmin ? [newAdmin] : [])
+      .rpc();
+  }
+
+  async function admin() {
+    return ((await program.account.programConfig.fetch(config)) as any).admin.toString();
+  }
+
+  it("Initializes the config once, with the upgrade authority as admin", async () => {
+    const configAccount = await program.account.programConfig.fetch(config) as any;
+    expect(configAccount.admin.toString()).to.equal(wallet.toString());
+    expect(configAccount.pendingAdmin).to.be.null;
+    expect(configAccount.treasury.toString()).to.equal(treasuryAddress(program).toString());
+    expect(configAccount.defaultFees.creatorFeeBps).to.equal(FEES.creatorFeeBps);
+    expect(configAccount.resolutionWindowSecs.toNumber()).to.equal(RESOLUTION_WINDOW_SECS);
+
+    const [programData] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    try {
+      await program.methods
+        .initializeConfig(FEES, FEES, new anchor.BN(1), new anchor.BN(1), configAccount.disputes, new anchor.BN(1))
+        .accounts({
+          config,
+          treasury: treasuryAddress(program),
+          program: program.programId,
+          programData,
+          admin: wallet,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("initialized the config twice");
+    } catch (err: any) {
+      expect(err.toString()).to.include("already in use");
+    }
+  });
+
+  it("Rotates the admin in two steps", async () => {
+    try {
+      await proposeAdmin(outsider, outsider.publicKey);
+      expect.fail("an outsider nominated themselves");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ConstraintHasOne");
+    }
+
+    await proposeAdmin(null, nominee.publicKey);
+    expect(((await program.account.programConfig.fetch(config)) as any).pendingAdmin.toString()).to.equal(
+      nominee.publicKey.toString()
+    );
+
+    try {
+      await acceptAdmin(outsider);
+      expect.fail("someone other than the nominee took over");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotPendingAdmin");
+    }
+    // Nominating doesn't hand anything over yet
+    expect(await admin()).to.equal(wallet.toString());
+
+    await acceptAdmin(nominee);
+    expect(await admin()).to.equal(nominee.publicKey.toString());
+    expect(((await program.account.programConfig.fetch(config)) as any).pendingAdmin).to.be.null;
+
+    // The previous admin is locked out
+    try {
+      await program.methods
+        .setClaimWindow(new anchor.BN(86400))
+        .accounts({ config, admin: wallet })
+        .rpc();
+      expect.fail("the previous admin changed the config");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ConstraintHasOne");
+    }
+
+    // Hand it back for the other suites
+    await proposeAdmin(nominee, wallet);
+    await acceptAdmin(null);
+    expect(await admin()).to.equal(wallet.toString());
+  });
+});
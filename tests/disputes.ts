@@ -0,0 +1,313 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  airdrop,
+  configAddress,
+  createDare,
+  disputeAddress,
+  betAddress,
+  ensureConfig,
+  pendingActionAddress,
+  placeBet,
+  poolAddress,
+  resolveDare,
+  RULING_WINDOW_SECS,
+  sleep,
+  treasuryAddress,
+} from "./helpers";
+
+describe("disputes", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const config = configAddress(program);
+  const treasury = treasuryAddress(program);
+
+  let creator: Keypair;
+  let willDoBettor: Keypair;
+  let wontDoBettor: Keypair;
+
+  before(async () => {
+    creator = Keypair.generate();
+    willDoBettor = Keypair.generate();
+    wontDoBettor = Keypair.generate();
+
+    await airdrop(provider, creator, willDoBettor, wontDoBettor);
+    await ensureConfig(program, provider);
+  });
+
+  /** A dare with a bet on each side, resolved by the admin as "will do" */
+  async function resolvedDare(title: string) {
+    const dare = await createDare(program, creator, title, { bettingSecs: 4 });
+    await placeBet(program, dare, willDoBettor, LAMPORTS_PER_SOL, "willDo");
+    await placeBet(program, dare, wontDoBettor, LAMPORTS_PER_SOL, "wontDo");
+
+    // Wait for the proof deadline
+    await sleep(5000);
+    await resolveDare(program, provider, dare, "willDo");
+    return dare;
+  }
+
+  async function openDispute(dare: PublicKey, challenger: Keypair) {
+    await program.methods
+      .openDispute()
+      .accounts({
+        dare,
+        bet: betAddress(program, dare, challenger.publicKey),
+        dispute: disputeAddress(program, dare),
+        config,
+        challenger: challenger.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([challenger])
+      .rpc();
+  }
+
+  async function resolveDispute(dare: PublicKey, challenger: Keypair, overturn: boolean) {
+    await program.methods
+      .resolveDispute(overturn)
+      .accounts({
+        dare,
+        dispute: disputeAddress(program, dare),
+        config,
+        treasury,
+        challenger: challenger.publicKey,
+        arbiter: provider.wallet.publicKey,
+      })
+      .rpc();
+  }
+
+  async function expireDispute(dare: PublicKey, challenger: Keypair) {
+    await program.methods
+      .expireDispute()
+      .accounts({
+        dare,
+        dispute: disputeAddress(program, dare),
+        challenger: challenger.publicKey,
+      })
+      .rpc();
+  }
+
+  it("Holds the bond and freezes claims while a dispute is open", async () => {
+    const dare = await resolvedDare("Bonded Dispute Dare");
+    const { bond } = (await program.account.programConfig.fetch(config) as any).disputes;
+    const dispute = disputeAddress(program, dare);
+
+    await openDispute(dare, wontDoBettor);
+
+    const disputeAccount = await program.account.dispute.fetch(dispute) as any;
+    expect(disputeAccount.challenger.toString()).to.equal(wontDoBettor.publicKey.toString());
+    expect(disputeAccount.bond.toString()).to.equal(bond.toString());
+    const rent = await provider.connection.getMinimumBalanceForRentExemption(
+      (await provider.connection.getAccountInfo(dispute))!.data.length
+    );
+    expect(await provider.connection.getBalance(dispute)).to.equal(rent + bond.toNumber());
+    expect((await program.account.dare.fetch(dare) as any).status).to.deep.equal({ disputed: {} });
+
+    try {
+      await program.methods
+        .claimWinnings()
+        .accounts({
+          dare,
+          bet: betAddress(program, dare, willDoBettor.publicKey),
+          poolAccount: poolAddress(program, dare),
+          config,
+          winner: willDoBettor.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([willDoBettor])
+        .rpc();
+      expect.fail("claimed while the outcome was disputed");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidDareStatus");
+    }
+
+    // Upheld: the challenger only gets the dispute account's rent back
+    const challengerBefore = await provider.connection.getBalance(wontDoBettor.publicKey);
+    await resolveDispute(dare, wontDoBettor, false);
+    const challengerAfter = await provider.connection.getBalance(wontDoBettor.publicKey);
+    expect(challengerAfter - challengerBefore).to.equal(rent);
+    expect(await program.account.dispute.fetchNullable(dispute)).to.be.null;
+
+    const dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.status).to.deep.equal({ completed: {} });
+    expect(dareAccount.outcome).to.deep.equal({ willDo: {} });
+  });
+
+  it("Only lets bettors open a dispute", async () => {
+    const dare = await resolvedDare("Outsider Dispute Dare");
+    try {
+      await openDispute(dare, creator);
+      expect.fail("an outsider disputed the outcome");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  it("Settles on the original outcome when the arbiter does not rule in time", async () => {
+    const dare = await resolvedDare("Expired Dispute Dare");
+    await openDispute(dare, wontDoBettor);
+
+    try {
+      await expireDispute(dare, wontDoBettor);
+      expect.fail("expired a dispute the arbiter could still rule on");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RulingWindowOpen");
+    }
+
+    await sleep((RULING_WINDOW_SECS + 1) * 1000);
+
+    try {
+      await resolveDispute(dare, wontDoBettor, true);
+      expect.fail("ruled after the ruling deadline");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RulingWindowClosed");
+    }
+
+    // Permissionless: the bond comes back and the original outcome stands
+    const { bond } = await program.account.dispute.fetch(disputeAddress(program, dare)) as any;
+    const challengerBefore = await provider.connection.getBalance(wontDoBettor.publicKey);
+    await expireDispute(dare, wontDoBettor);
+    const challengerAfter = await provider.connection.getBalance(wontDoBettor.publicKey);
+    expect(challengerAfter - challengerBefore).to.be.greaterThan(bond.toNumber());
+
+    const dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.status).to.deep.equal({ completed: {} });
+    expect(dareAccount.outcome).to.deep.equal({ willDo: {} });
+  });
+
+  it("Lets an emergency refund rescue a disputed dare", async () => {
+    const dare = await resolvedDare("Halted Dispute Dare");
+    await openDispute(dare, wontDoBettor);
+
+    await program.methods
+      .emergencyRefund()
+      .accounts({
+        dare,
+        poolAccount: poolAddress(program, dare),
+        config,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+    expect((await program.account.dare.fetch(dare) as any).status).to.deep.equal({ emergencyRefunding: {} });
+
+    try {
+      await resolveDispute(dare, wontDoBettor, true);
+      expect.fail("ruled on a halted dare");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidDareStatus");
+    }
+
+    // No need to wait for the ruling deadline to get the bond back
+    await expireDispute(dare, wontDoBettor);
+    expect(await program.account.dispute.fetchNullable(disputeAddress(program, dare))).to.be.null;
+  });
+
+  it("Holds dispute config changes until the timelock has passed", async () => {
+    const before = await program.account.programConfig.fetch(config) as any;
+    const actionId = new anchor.BN(Date.now());
+    const pendingAction = pendingActionAddress(program, actionId);
+
+    // Making someone else the arbiter with a huge reward must wait like a withdrawal
+    await program.methods
+      .queueDisputeConfig(actionId, {
+        arbiter: creator.publicKey,
+        windowSecs: before.disputes.windowSecs,
+        rulingWindowSecs: before.disputes.rulingWindowSecs,
+        bond: before.disputes.bond,
+        reward: new anchor.BN(1000 * LAMPORTS_PER_SOL),
+      })
+      .accounts({
+        config,
+        pendingAction,
+        admin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .executePendingAction()
+        .accounts({
+          config,
+          pendingAction,
+          treasury,
+          recipient: null,
+          mint: null,
+          treasuryTokens: null,
+          tokenProgram: null,
+          admin: provider.wallet.publicKey,
+          executor: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+      expect.fail("changed the dispute config before its timelock");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("TimelockNotElapsed");
+    }
+
+    const after = await program.account.programConfig.fetch(config) as any;
+    expect(after.disputes.arbiter.toString()).to.equal(before.disputes.arbiter.toString());
+    expect(after.disputes.reward.toString()).to.equal(before.disputes.reward.toString());
+
+    await program.methods
+      .cancelPendingAction()
+      .accounts({ config, pendingAction, admin: provider.wallet.publicKey })
+      .rpc();
+  });
+
+  it("Pays dispute rewards only out of slashed bonds", async () => {
+    const { bond, reward } = (await program.account.programConfig.fetch(config) as any).disputes;
+
+    // An upheld dispute slashes the bond into the reserve
+    const upheld = await resolvedDare("Upheld Dispute Dare");
+    await openDispute(upheld, wontDoBettor);
+    let reserveBefore = (await program.account.programConfig.fetch(config) as any).disputeReserve;
+    let treasuryBefore = await provider.connection.getBalance(treasury);
+    await resolveDispute(upheld, wontDoBettor, false);
+
+    let reserveAfter = (await program.account.programConfig.fetch(config) as any).disputeReserve;
+    let treasuryAfter = await provider.connection.getBalance(treasury);
+    expect(reserveAfter.sub(reserveBefore).toString()).to.equal(bond.toString());
+    expect(treasuryAfter - treasuryBefore).to.equal(bond.toNumber());
+
+    // An overturned one pays its reward out of the reserve, and nothing else leaves the treasury
+    const overturned = await resolvedDare("Overturned Dispute Dare");
+    await openDispute(overturned, wontDoBettor);
+    reserveBefore = reserveAfter;
+    treasuryBefore = treasuryAfter;
+    await resolveDispute(overturned, wontDoBettor, true);
+
+    const paid = anchor.BN.min(reward, reserveBefore);
+    reserveAfter = (await program.account.programConfig.fetch(config) as any).disputeReserve;
+    treasuryAfter = await provider.connection.getBalance(treasury);
+    expect(reserveBefore.sub(reserveAfter).toString()).to.equal(paid.toString());
+    expect(treasuryBefore - treasuryAfter).to.equal(paid.toNumber());
+
+    const dareAccount = await program.account.dare.fetch(overturned) as any;
+    expect(dareAccount.outcome).to.deep.equal({ wontDo: {} });
+    expect(dareAccount.status).to.deep.equal({ failed: {} });
+  });
+
+  it("Rejects a zero-amount treasury withdrawal", async () => {
+    const actionId = new anchor.BN(Date.now());
+    try {
+      await program.methods
+        .queueTreasuryWithdrawal(actionId, null, creator.publicKey, new anchor.BN(0))
+        .accounts({
+          config,
+          pendingAction: pendingActionAddress(program, actionId),
+          admin: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("queued an empty withdrawal");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidAmount");
+    }
+  });
+});
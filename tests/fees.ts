@@ -0,0 +1,157 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  airdrop,
+  configAddress,
+  createDare,
+  DISPUTE_WINDOW_SECS,
+  ensureConfig,
+  FEES,
+  placeBet,
+  poolAddress,
+  resolveDare,
+  sleep,
+  sleepUntil,
+  treasuryAddress,
+} from "./helpers";
+
+describe("fees", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const config = configAddress(program);
+
+  const treasury = treasuryAddress(program);
+
+  let creator: Keypair;
+  let bettor: Keypair;
+
+  before(async () => {
+    creator = Keypair.generate();
+    bettor = Keypair.generate();
+
+    await airdrop(provider, creator, bettor);
+    await ensureConfig(program, provider);
+  });
+
+  async function setFeeSchedule(defaultFees: typeof FEES, maxFees: typeof FEES) {
+    await program.methods
+      .setFeeSchedule(defaultFees, maxFees)
+      .accounts({ config, admin: provider.wallet.publicKey })
+      .rpc();
+  }
+
+  async function dareFees(dare: anchor.web3.PublicKey) {
+    return ((await program.account.dare.fetch(dare)) as any).fees;
+  }
+
+  it("Lets creators pick their own fees within the admin's limits", async () => {
+    const dare = await createDare(program, creator, "Low Fee Dare", {
+      feeOverride: {
+        ...FEES,
+        creatorFeeBps: 100,
+        firstPlaceBps: 2000,
+        // Not up to the creator: replaced by the defaults
+        protocolFeeBps: 0,
+        cashOutTreasuryBps: 0,
+      },
+    });
+
+    const fees = await dareFees(dare);
+    expect(fees.creatorFeeBps).to.equal(100);
+    expect(fees.firstPlaceBps).to.equal(2000);
+    expect(fees.protocolFeeBps).to.equal(FEES.protocolFeeBps);
+    expect(fees.cashOutTreasuryBps).to.equal(FEES.cashOutTreasuryBps);
+    expect(fees.refundFeeBps).to.equal(FEES.refundFeeBps);
+  });
+
+  it("Rejects overrides above the limits", async () => {
+    try {
+      await createDare(program, creator, "High Fee Dare", {
+        feeOverride: { ...FEES, creatorFeeBps: FEES.creatorFeeBps + 1 },
+      });
+      expect.fail("created a dare above the fee limits");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("FeeAboveLimit");
+    }
+  });
+
+  it("Applies new defaults to new dares only", async () => {
+    const before = await createDare(program, creator, "Old Schedule Dare");
+
+    try {
+      await setFeeSchedule({ ...FEES, creatorFeeBps: FEES.creatorFeeBps + 1 }, FEES);
+      expect.fail("set defaults above the limits");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("FeeAboveLimit");
+    }
+    try {
+      const overpaid = { ...FEES, firstPlaceBps: 5000, secondPlaceBps: 5000, thirdPlaceBps: 1 };
+      await setFeeSchedule(overpaid, overpaid);
+      expect.fail("set winner rewards above 100%");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidFeeBps");
+    }
+
+    await setFeeSchedule({ ...FEES, creatorFeeBps: 150 }, FEES);
+    try {
+      const after = await createDare(program, creator, "New Schedule Dare");
+      expect((await dareFees(after)).creatorFeeBps).to.equal(150);
+      expect((await dareFees(before)).creatorFeeBps).to.equal(FEES.creatorFeeBps);
+    } finally {
+      await setFeeSchedule(FEES, FEES);
+    }
+  });
+
+  it("Collects the protocol fee into the treasury once", async () => {
+    const dare = await createDare(program, creator, "Protocol Fee Dare", { bettingSecs: 4 });
+    await placeBet(program, dare, bettor, 2 * LAMPORTS_PER_SOL, "willDo");
+    await placeBet(program, dare, creator, LAMPORTS_PER_SOL, "wontDo");
+
+    const collect = (treasuryAccount = treasury) =>
+      program.methods
+        .collectProtocolFee()
+        .accounts({ dare, poolAccount: poolAddress(program, dare), config, treasury: treasuryAccount })
+        .rpc();
+
+    const { proofDeadline } = await program.account.dare.fetch(dare) as any;
+    await sleepUntil(proofDeadline);
+    await resolveDare(program, provider, dare, "willDo");
+
+    try {
+      await collect();
+      expect.fail("collected during the dispute window");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("DisputeWindowOpen");
+    }
+    await sleep((DISPUTE_WINDOW_SECS + 1) * 1000);
+
+    try {
+      await collect(creator.publicKey);
+      expect.fail("sent the protocol fee somewhere other than the treasury");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ConstraintHasOne");
+    }
+
+    // Permissionless, and always the dare's own protocol fee
+    const before = await provider.connection.getBalance(treasury);
+    await collect();
+    const after = await provider.connection.getBalance(treasury);
+    expect(after - before).to.equal((3 * LAMPORTS_PER_SOL * FEES.protocolFeeBps) / 10000);
+
+    const dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.protocolFeeCollected).to.be.true;
+    expect(dareAccount.totalClaimed.toNumber()).to.equal(after - before);
+
+    try {
+      await collect();
+      expect.fail("collected the protocol fee twice");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ProtocolFeeAlreadyCollected");
+    }
+  });
+});
@@ -0,0 +1,314 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  LAMPORTS_PER_SOL,
+} from "@solana/web3.js";
+
+const BPF_LOADER_UPGRADEABLE = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
+export const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+/** Sleeps until a second after the unix timestamp `at`, to let the validator's clock catch up */
+export async function sleepUntil(at: number | anchor.BN) {
+  const ms = Number(at) * 1000 + 1000 - Date.now();
+  if (ms > 0) {
+    await sleep(ms);
+  }
+}
+
+export const FEES = {
+  creatorFeeBps: 200,
+  protocolFeeBps: 100,
+  firstPlaceBps: 3000,
+  secondPlaceBps: 1500,
+  thirdPlaceBps: 500,
+  cashOutPenaltyBps: 1000,
+  cashOutTreasuryBps: 5000,
+  refundFeeBps: 0,
+};
+
+// Short enough to wait out in a test, long enough to open (or rule on) a dispute in
+export const DISPUTE_WINDOW_SECS = 3;
+export const RULING_WINDOW_SECS = 3;
+// Long enough for the admin to resolve in, short enough to wait for the fallback
+export const RESOLUTION_WINDOW_SECS = 10;
+// Short enough to wait out, long enough to try executing an action before it has passed
+export const WITHDRAWAL_TIMELOCK_SECS = 5;
+
+export function configAddress(program: Program<any>) {
+  return PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId)[0];
+}
+
+export function treasuryAddress(program: Program<any>) {
+  return PublicKey.findProgramAddressSync([Buffer.from("treasury")], program.programId)[0];
+}
+
+export function dareAddress(program: Program<any>, creator: PublicKey, title: string) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("dare"), creator.toBuffer(), Buffer.from(title)],
+    program.programId
+  )[0];
+}
+
+export function poolAddress(program: Program<any>, dare: PublicKey) {
+  return PublicKey.findProgramAddressSync([Buffer.from("pool"), dare.toBuffer()], program.programId)[0];
+}
+
+export function vaultAddress(program: Program<any>, dare: PublicKey) {
+  return PublicKey.findProgramAddressSync([Buffer.from("vault"), dare.toBuffer()], program.programId)[0];
+}
+
+export function betAddress(program: Program<any>, dare: PublicKey, bettor: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("bet"), dare.toBuffer(), bettor.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export function proofAddress(program: Program<any>, dare: PublicKey, index: number) {
+  const indexBytes = Buffer.alloc(4);
+  indexBytes.writeUInt32LE(index);
+  return PublicKey.findProgramAddressSync([Buffer.from("proof"), dare.toBuffer(), indexBytes], program.programId)[0];
+}
+
+export function likeAddress(program: Program<any>, proofSubmission: PublicKey, liker: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("like"), proofSubmission.toBuffer(), liker.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export function allowedMintAddress(program: Program<any>, mint: PublicKey) {
+  return PublicKey.findProgramAddressSync([Buffer.from("allowed_mint"), mint.toBuffer()], program.programId)[0];
+}
+
+export function committeeAddress(program: Program<any>, committeeId: anchor.BN) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("committee"), committeeId.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  )[0];
+}
+
+export function disputeAddress(program: Program<any>, dare: PublicKey) {
+  return PublicKey.findProgramAddressSync([Buffer.from("dispute"), dare.toBuffer()], program.programId)[0];
+}
+
+export function pendingActionAddress(program: Program<any>, actionId: anchor.BN) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("pending_action"), configAddress(program).toBuffer(), actionId.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  )[0];
+}
+
+export async function airdrop(provider: anchor.AnchorProvider, ...keypairs: Keypair[]) {
+  const airdropAmount = 10 * LAMPORTS_PER_SOL;
+  await Promise.all(
+    keypairs.map(async (keypair) =>
+      provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(keypair.publicKey, airdropAmount)
+      )
+    )
+  );
+}
+
+/**
+ * The config is a singleton shared by every suite: create it with the test
+ * fixture unless another suite already did, and return it.
+ */
+export async function ensureConfig(program: Program<any>, provider: anchor.AnchorProvider) {
+  const config = configAddress(program);
+  const existing = await program.account.programConfig.fetchNullable(config);
+  if (existing) {
+    return existing as any;
+  }
+
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE
+  );
+
+  await program.methods
+    .initializeConfig(FEES, FEES, new anchor.BN(86400), new anchor.BN(RESOLUTION_WINDOW_SECS), {
+      arbiter: provider.wallet.publicKey,
+      windowSecs: new anchor.BN(DISPUTE_WINDOW_SECS),
+      rulingWindowSecs: new anchor.BN(RULING_WINDOW_SECS),
+      bond: new anchor.BN(0.1 * LAMPORTS_PER_SOL),
+      reward: new anchor.BN(0.05 * LAMPORTS_PER_SOL),
+    }, new anchor.BN(WITHDRAWAL_TIMELOCK_SECS))
+    .accounts({
+      config,
+      treasury: treasuryAddress(program),
+      program: program.programId,
+      programData,
+      admin: provider.wallet.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+
+  return await program.account.programConfig.fetch(config) as any;
+}
+
+// Events are emitted through a self-CPI: decode the inner instructions, skipping
+// the 8-byte event instruction tag
+export async function cpiEvents(
+  program: Program<any>,
+  provider: anchor.AnchorProvider,
+  signature: string
+) {
+  const tx = await provider.connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  return tx!.meta!.innerInstructions!
+    .flatMap((inner) => inner.instructions)
+    .map((ix) => anchor.utils.bytes.bs58.decode(ix.data))
+    .map((data) => program.coder.events.decode(anchor.utils.bytes.base64.encode(data.subarray(8))))
+    .filter((event) => event !== null);
+}
+
+export type DareOptions = {
+  bettingSecs?: number; // Seconds from now until betting closes
+  proofSecs?: number; // Seconds from now until the proof deadline
+  resolutionSecs?: number; // Seconds from now until the resolution deadline, at least the config's window after the proof deadline
+  minBet?: anchor.BN;
+  feeOverride?: typeof FEES | null;
+  resolutionMode?: object;
+  rankingMode?: object;
+  committee?: PublicKey; // The committee a committee-resolved dare names
+};
+
+/** Creates a SOL dare from `creator`, by default open for an hour, resolved and ranked by the admin */
+export async function createDare(
+  program: Program<any>,
+  creator: Keypair,
+  title: string,
+  options: DareOptions = {}
+) {
+  const config = await program.account.programConfig.fetch(configAddress(program)) as any;
+  const now = Math.floor(Date.now() / 1000);
+  const bettingSecs = options.bettingSecs ?? 3600;
+  const proofSecs = options.proofSecs ?? bettingSecs;
+  const resolutionSecs = options.resolutionSecs ?? proofSecs + config.resolutionWindowSecs.toNumber();
+
+  const dare = dareAddress(program, creator.publicKey, title);
+  await program.methods
+    .createDare(
+      title,
+      `Description of ${title}`,
+      {
+        bettingClosesAt: new anchor.BN(now + bettingSecs),
+        proofDeadline: new anchor.BN(now + proofSecs),
+        resolutionDeadline: new anchor.BN(now + resolutionSecs),
+      },
+      options.minBet ?? new anchor.BN(0.1 * LAMPORTS_PER_SOL),
+      options.feeOverride ?? null,
+      options.resolutionMode ?? { admin: {} },
+      options.rankingMode ?? { admin: {} }
+    )
+    .accounts({
+      dare,
+      poolAccount: poolAddress(program, dare),
+      committee: options.committee ?? null,
+      config: configAddress(program),
+      creator: creator.publicKey,
+      systemProgram: SystemProgram.programId,
+      rent: SYSVAR_RENT_PUBKEY,
+    })
+    .signers([creator])
+    .rpc();
+
+  return dare;
+}
+
+export async function placeBet(
+  program: Program<any>,
+  dare: PublicKey,
+  bettor: Keypair,
+  amount: number | anchor.BN,
+  side: "willDo" | "wontDo"
+) {
+  return program.methods
+    .placeBet(new anchor.BN(amount), { [side]: {} })
+    .accounts({
+      dare,
+      bet: betAddress(program, dare, bettor.publicKey),
+      poolAccount: poolAddress(program, dare),
+      config: configAddress(program),
+      bettor: bettor.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([bettor])
+    .rpc();
+}
+
+/** Submits a proof for `dare` from `submitter` and returns its address */
+export async function submitProof(program: Program<any>, dare: PublicKey, submitter: Keypair) {
+  const { submissionCount } = await program.account.dare.fetch(dare) as any;
+  const proofSubmission = proofAddress(program, dare, submissionCount);
+  await program.methods
+    .submitProof(`proof-${submissionCount}`, "Proof of the dare")
+    .accounts({
+      dare,
+      proofSubmission,
+      config: configAddress(program),
+      submitter: submitter.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([submitter])
+    .rpc();
+  return proofSubmission;
+}
+
+/** The admin approves `proofSubmission` */
+export async function approveProof(
+  program: Program<any>,
+  provider: anchor.AnchorProvider,
+  dare: PublicKey,
+  proofSubmission: PublicKey
+) {
+  return program.methods
+    .approveProof()
+    .accounts({
+      dare,
+      proofSubmission,
+      config: configAddress(program),
+      platformAuthority: provider.wallet.publicKey,
+    })
+    .rpc();
+}
+
+export async function likeProof(program: Program<any>, dare: PublicKey, proofSubmission: PublicKey, liker: Keypair) {
+  return program.methods
+    .likeProofSubmission()
+    .accounts({
+      proofSubmission,
+      dare,
+      like: likeAddress(program, proofSubmission, liker.publicKey),
+      liker: liker.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([liker])
+    .rpc();
+}
+
+/** The admin resolves `dare`, which must be past its proof deadline */
+export async function resolveDare(
+  program: Program<any>,
+  provider: anchor.AnchorProvider,
+  dare: PublicKey,
+  outcome: "willDo" | "wontDo"
+) {
+  return program.methods
+    .resolveDare({ [outcome]: {} })
+    .accounts({
+      dare,
+      config: configAddress(program),
+      oracle: null,
+      resolver: provider.wallet.publicKey,
+    })
+    .rpc();
+}
@@ -0,0 +1,107 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import {
+  airdrop,
+  createDare,
+  ensureConfig,
+  likeAddress,
+  likeProof,
+  sleep,
+  submitProof,
+} from "./helpers";
+
+describe("likes", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  let creator: Keypair;
+  let submitter: Keypair;
+  let liker: Keypair;
+
+  before(async () => {
+    creator = Keypair.generate();
+    submitter = Keypair.generate();
+    liker = Keypair.generate();
+
+    await airdrop(provider, creator, submitter, liker);
+    await ensureConfig(program, provider);
+  });
+
+  async function unlikeProof(dare: PublicKey, proofSubmission: PublicKey, unliker: Keypair) {
+    await program.methods
+      .unlikeProofSubmission()
+      .accounts({
+        proofSubmission,
+        dare,
+        like: likeAddress(program, proofSubmission, unliker.publicKey),
+        liker: unliker.publicKey,
+      })
+      .signers([unliker])
+      .rpc();
+  }
+
+  async function likesCount(proofSubmission: PublicKey) {
+    return ((await program.account.proofSubmission.fetch(proofSubmission)) as any).likesCount;
+  }
+
+  it("Counts one like per wallet", async () => {
+    const dare = await createDare(program, creator, "Liked Dare");
+    const proofSubmission = await submitProof(program, dare, submitter);
+
+    await likeProof(program, dare, proofSubmission, liker);
+    expect(await likesCount(proofSubmission)).to.equal(1);
+
+    try {
+      await likeProof(program, dare, proofSubmission, liker);
+      expect.fail("liked the same submission twice");
+    } catch (err: any) {
+      expect(err.toString()).to.include("already in use");
+    }
+    expect(await likesCount(proofSubmission)).to.equal(1);
+  });
+
+  it("Takes a like back on unlike, and only if there was one", async () => {
+    const dare = await createDare(program, creator, "Unliked Dare");
+    const proofSubmission = await submitProof(program, dare, submitter);
+
+    try {
+      await unlikeProof(dare, proofSubmission, liker);
+      expect.fail("unliked without a like");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+
+    await likeProof(program, dare, proofSubmission, liker);
+    await unlikeProof(dare, proofSubmission, liker);
+    expect(await likesCount(proofSubmission)).to.equal(0);
+    expect(
+      await program.account.like.fetchNullable(likeAddress(program, proofSubmission, liker.publicKey))
+    ).to.be.null;
+  });
+
+  it("Freezes likes at the proof deadline", async () => {
+    const dare = await createDare(program, creator, "Frozen Likes Dare", { bettingSecs: 4 });
+    const proofSubmission = await submitProof(program, dare, submitter);
+    await likeProof(program, dare, proofSubmission, liker);
+
+    await sleep(5000);
+
+    try {
+      await unlikeProof(dare, proofSubmission, liker);
+      expect.fail("unliked after the proof deadline");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidDareStatus");
+    }
+    try {
+      await likeProof(program, dare, proofSubmission, submitter);
+      expect.fail("liked after the proof deadline");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidDareStatus");
+    }
+    expect(await likesCount(proofSubmission)).to.equal(1);
+  });
+});
@@ -0,0 +1,195 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  LAMPORTS_PER_SOL,
+} from "@solana/web3.js";
+import { airdrop, configAddress, ensureConfig, sleep } from "./helpers";
+
+describe("oracle resolution", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const mockOracle = anchor.workspace.MockOracle as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const config = configAddress(program);
+
+  let creator: Keypair;
+  let bettor: Keypair;
+  let resolutionWindowSecs: number;
+
+  before(async () => {
+    creator = Keypair.generate();
+    bettor = Keypair.generate();
+
+    await airdrop(provider, creator, bettor);
+
+    const existing = await ensureConfig(program, provider);
+    resolutionWindowSecs = existing.resolutionWindowSecs.toNumber();
+  });
+
+  async function createOracleDare(title: string, oracle: PublicKey) {
+    const now = Math.floor(Date.now() / 1000);
+    const schedule = {
+      bettingClosesAt: new anchor.BN(now + 3),
+      proofDeadline: new anchor.BN(now + 5),
+      resolutionDeadline: new anchor.BN(now + 5 + resolutionWindowSecs),
+    };
+
+    const [dare] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dare"), creator.publicKey.toBuffer(), Buffer.from(title)],
+      program.programId
+    );
+    const [poolAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), dare.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createDare(
+        title,
+        "Resolved from a data feed",
+        schedule,
+        new anchor.BN(0.1 * LAMPORTS_PER_SOL),
+        null,
+        { oracle: { oracle, program: mockOracle.programId } },
+        { admin: {} }
+      )
+      .accounts({
+        dare,
+        poolAccount,
+        config,
+        creator: creator.publicKey,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([creator])
+      .rpc();
+
+    const [bet] = PublicKey.findProgramAddressSync(
+      [Buffer.from("bet"), dare.toBuffer(), bettor.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .placeBet(new anchor.BN(LAMPORTS_PER_SOL), { willDo: {} })
+      .accounts({
+        dare,
+        bet,
+        poolAccount,
+        config,
+        bettor: bettor.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bettor])
+      .rpc();
+
+    return dare;
+  }
+
+  async function createOracleOutcome() {
+    const outcome = Keypair.generate();
+    await mockOracle.methods
+      .initializeOutcome()
+      .accounts({
+        outcome: outcome.publicKey,
+        authority: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([outcome])
+      .rpc();
+    return outcome.publicKey;
+  }
+
+  it("Resolves a dare from the oracle's reported outcome", async () => {
+    const oracle = await createOracleOutcome();
+    const dare = await createOracleDare("Oracle Dare", oracle);
+
+    // Wait for the proof deadline
+    await sleep(6000);
+
+    try {
+      await program.methods
+        .resolveFromOracle()
+        .accounts({ dare, oracle, config })
+        .rpc();
+      expect.fail("resolved before the oracle reported");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("OracleNotResolved");
+    }
+
+    await mockOracle.methods
+      .setOutcome(true)
+      .accounts({ outcome: oracle, authority: provider.wallet.publicKey })
+      .rpc();
+
+    // Permissionless: any signer can pay for the resolution
+    await program.methods
+      .resolveFromOracle()
+      .accounts({ dare, oracle, config })
+      .rpc();
+
+    const dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.status).to.deep.equal({ completed: {} });
+    expect(dareAccount.outcome).to.deep.equal({ willDo: {} });
+  });
+
+  it("Lets an outcome reported after the resolution deadline win over the fallback", async () => {
+    const oracle = await createOracleOutcome();
+    const dare = await createOracleDare("Late Oracle Dare", oracle);
+
+    // Past the resolution deadline, and the oracle only reports now
+    await sleep((6 + resolutionWindowSecs) * 1000);
+    await mockOracle.methods
+      .setOutcome(true)
+      .accounts({ outcome: oracle, authority: provider.wallet.publicKey })
+      .rpc();
+
+    const resolve = (oracleAccount: PublicKey | null) =>
+      program.methods
+        .resolveDare({ wontDo: {} })
+        .accounts({ dare, config, oracle: oracleAccount, resolver: bettor.publicKey })
+        .signers([bettor])
+        .rpc();
+
+    try {
+      await resolve(null);
+      expect.fail("fell back without reading the oracle");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidOracleAccount");
+    }
+
+    // Anyone can resolve now, but not against what the oracle reported
+    await resolve(oracle);
+
+    const dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.status).to.deep.equal({ completed: {} });
+    expect(dareAccount.outcome).to.deep.equal({ willDo: {} });
+  });
+
+  it("Rejects an oracle account the dare did not name", async () => {
+    const oracle = await createOracleOutcome();
+    const impostor = await createOracleOutcome();
+    const dare = await createOracleDare("Oracle Impostor Dare", oracle);
+
+    await mockOracle.methods
+      .setOutcome(true)
+      .accounts({ outcome: impostor, authority: provider.wallet.publicKey })
+      .rpc();
+    await sleep(6000);
+
+    try {
+      await program.methods
+        .resolveFromOracle()
+        .accounts({ dare, oracle: impostor, config })
+        .rpc();
+      expect.fail("resolved from the wrong oracle account");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidOracleAccount");
+    }
+  });
+});
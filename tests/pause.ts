@@ -0,0 +1,226 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  airdrop,
+  betAddress,
+  configAddress,
+  createDare,
+  DISPUTE_WINDOW_SECS,
+  ensureConfig,
+  placeBet,
+  poolAddress,
+  resolveDare,
+  sleep,
+  sleepUntil,
+  submitProof,
+  treasuryAddress,
+} from "./helpers";
+
+// The PAUSE_* bits in lib.rs
+const PAUSE_BETTING = 1 << 0;
+const PAUSE_PROOF_SUBMISSION = 1 << 1;
+const PAUSE_CLAIMING = 1 << 2;
+const PAUSE_CASH_OUT = 1 << 3;
+
+// Short enough to wait out in a test
+const CLAIM_WINDOW_SECS = 8;
+
+describe("pause", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const config = configAddress(program);
+  const treasury = treasuryAddress(program);
+
+  let creator: Keypair;
+  let bettors: Keypair[];
+
+  before(async () => {
+    creator = Keypair.generate();
+    bettors = [Keypair.generate(), Keypair.generate()];
+
+    await airdrop(provider, creator, ...bettors);
+    await ensureConfig(program, provider);
+  });
+
+  async function setPaused(dare: PublicKey | null, flags: number) {
+    await program.methods
+      .setPaused(dare ? { dare: {} } : { global: {} }, flags)
+      .accounts({ config, dare, admin: provider.wallet.publicKey })
+      .rpc();
+  }
+
+  async function expectPaused(action: Promise<unknown>, what: string) {
+    try {
+      await action;
+      expect.fail(`${what} while paused`);
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Paused");
+    }
+  }
+
+  const cashOut = (dare: PublicKey, bettor: Keypair) =>
+    program.methods
+      .cashOutEarly()
+      .accounts({
+        dare,
+        bet: betAddress(program, dare, bettor.publicKey),
+        poolAccount: poolAddress(program, dare),
+        config,
+        treasury,
+        bettor: bettor.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bettor])
+      .rpc();
+
+  const claimWinnings = (dare: PublicKey, winner: Keypair) =>
+    program.methods
+      .claimWinnings()
+      .accounts({
+        dare,
+        bet: betAddress(program, dare, winner.publicKey),
+        poolAccount: poolAddress(program, dare),
+        config,
+        winner: winner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([winner])
+      .rpc();
+
+  const claimRefund = (dare: PublicKey, bettor: Keypair) =>
+    program.methods
+      .claimRefund()
+      .accounts({
+        dare,
+        bet: betAddress(program, dare, bettor.publicKey),
+        poolAccount: poolAddress(program, dare),
+        config,
+        treasury,
+        bettor: bettor.publicKey,
+      })
+      .signers([bettor])
+      .rpc();
+
+  const sweepDust = (dare: PublicKey) =>
+    program.methods
+      .sweepDust()
+      .accounts({ dare, poolAccount: poolAddress(program, dare), config, treasury })
+      .rpc();
+
+  it("Rejects unknown flags and a dare pause without the dare", async () => {
+    try {
+      await setPaused(null, 1 << 4);
+      expect.fail("set an unknown pause flag");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidPauseFlags");
+    }
+
+    try {
+      await program.methods
+        .setPaused({ dare: {} }, PAUSE_BETTING)
+        .accounts({ config, dare: null, admin: provider.wallet.publicKey })
+        .rpc();
+      expect.fail("paused a dare without naming it");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("MissingPausedDare");
+    }
+  });
+
+  it("Pauses betting on every dare at once", async () => {
+    const dare = await createDare(program, creator, "Globally Paused Dare");
+
+    await setPaused(null, PAUSE_BETTING);
+    try {
+      await expectPaused(createDare(program, creator, "Dare Created While Paused"), "created a dare");
+      await expectPaused(placeBet(program, dare, bettors[0], LAMPORTS_PER_SOL, "willDo"), "placed a bet");
+    } finally {
+      await setPaused(null, 0);
+    }
+
+    await placeBet(program, dare, bettors[0], LAMPORTS_PER_SOL, "willDo");
+  });
+
+  it("Pauses betting, proofs and cash-outs on a single dare", async () => {
+    const paused = await createDare(program, creator, "Paused Dare");
+    const other = await createDare(program, creator, "Unpaused Dare");
+    await placeBet(program, paused, bettors[0], LAMPORTS_PER_SOL, "willDo");
+
+    await setPaused(paused, PAUSE_BETTING | PAUSE_PROOF_SUBMISSION | PAUSE_CASH_OUT);
+    expect((await program.account.dare.fetch(paused) as any).paused).to.equal(
+      PAUSE_BETTING | PAUSE_PROOF_SUBMISSION | PAUSE_CASH_OUT
+    );
+
+    await expectPaused(placeBet(program, paused, bettors[1], LAMPORTS_PER_SOL, "wontDo"), "placed a bet");
+    await expectPaused(submitProof(program, paused, bettors[1]), "submitted a proof");
+    await expectPaused(cashOut(paused, bettors[0]), "cashed out");
+
+    // Other dares carry on
+    await placeBet(program, other, bettors[1], LAMPORTS_PER_SOL, "wontDo");
+    await submitProof(program, other, bettors[1]);
+
+    // Each bit is lifted on its own
+    await setPaused(paused, PAUSE_BETTING | PAUSE_PROOF_SUBMISSION);
+    await cashOut(paused, bettors[0]);
+    await expectPaused(submitProof(program, paused, bettors[1]), "submitted a proof");
+
+    await setPaused(paused, 0);
+    await submitProof(program, paused, bettors[1]);
+  });
+
+  it("Holds claims, refunds and sweeps while claiming is paused, then restarts the claim window", async () => {
+    const { claimWindowSecs } = await program.account.programConfig.fetch(config) as any;
+    await program.methods
+      .setClaimWindow(new anchor.BN(CLAIM_WINDOW_SECS))
+      .accounts({ config, admin: provider.wallet.publicKey })
+      .rpc();
+
+    try {
+      // One dare with a winner, one with nobody on the winning side
+      const settled = await createDare(program, creator, "Claims Paused Dare", { bettingSecs: 4 });
+      const refunding = await createDare(program, creator, "Refunds Paused Dare", { bettingSecs: 4 });
+      await placeBet(program, settled, bettors[0], LAMPORTS_PER_SOL, "willDo");
+      await placeBet(program, settled, bettors[1], LAMPORTS_PER_SOL, "wontDo");
+      await placeBet(program, refunding, bettors[0], LAMPORTS_PER_SOL, "willDo");
+
+      const { proofDeadline } = await program.account.dare.fetch(refunding) as any;
+      await sleepUntil(proofDeadline);
+      await resolveDare(program, provider, settled, "willDo");
+      await resolveDare(program, provider, refunding, "wontDo");
+      await sleep((DISPUTE_WINDOW_SECS + 1) * 1000);
+
+      await setPaused(null, PAUSE_CLAIMING);
+      try {
+        await expectPaused(claimWinnings(settled, bettors[0]), "claimed winnings");
+        await expectPaused(claimRefund(refunding, bettors[0]), "claimed a refund");
+        await expectPaused(sweepDust(settled), "swept the pool");
+
+        // Let the claim window run out while nobody can claim
+        const { disputeDeadline } = await program.account.dare.fetch(settled) as any;
+        await sleepUntil(disputeDeadline.toNumber() + CLAIM_WINDOW_SECS);
+      } finally {
+        await setPaused(null, 0);
+      }
+
+      // Bettors get a full claim window from the unpause
+      try {
+        await sweepDust(settled);
+        expect.fail("swept unclaimed winnings right after claims resumed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ClaimsOutstanding");
+      }
+
+      await claimWinnings(settled, bettors[0]);
+      await claimRefund(refunding, bettors[0]);
+    } finally {
+      await program.methods
+        .setClaimWindow(claimWindowSecs)
+        .accounts({ config, admin: provider.wallet.publicKey })
+        .rpc();
+    }
+  });
+});
@@ -0,0 +1,187 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  airdrop,
+  approveProof,
+  configAddress,
+  createDare,
+  ensureConfig,
+  likeProof,
+  placeBet,
+  resolveDare,
+  sleepUntil,
+  submitProof,
+} from "./helpers";
+
+describe("rankings", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const config = configAddress(program);
+
+  let creator: Keypair;
+  let bettor: Keypair;
+  let submitters: Keypair[];
+  let likers: Keypair[];
+
+  before(async () => {
+    creator = Keypair.generate();
+    bettor = Keypair.generate();
+    submitters = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    likers = [Keypair.generate(), Keypair.generate()];
+
+    await airdrop(provider, creator, bettor, ...submitters, ...likers);
+    await ensureConfig(program, provider);
+  });
+
+  /** A dare with `count` approved submissions and a bet on each side, open for a few more seconds */
+  async function dareWithSubmissions(title: string, rankingMode: object, count = 3) {
+    const dare = await createDare(program, creator, title, { bettingSecs: 10, rankingMode });
+    await placeBet(program, dare, bettor, LAMPORTS_PER_SOL, "willDo");
+    await placeBet(program, dare, creator, LAMPORTS_PER_SOL, "wontDo");
+
+    const submissions: PublicKey[] = [];
+    for (const submitter of submitters.slice(0, count)) {
+      const proofSubmission = await submitProof(program, dare, submitter);
+      await approveProof(program, provider, dare, proofSubmission);
+      submissions.push(proofSubmission);
+    }
+    return { dare, submissions };
+  }
+
+  /** Waits out the proof deadline and resolves `dare` as completed */
+  async function complete(dare: PublicKey) {
+    const { proofDeadline } = await program.account.dare.fetch(dare) as any;
+    await sleepUntil(proofDeadline);
+    await resolveDare(program, provider, dare, "willDo");
+  }
+
+  async function finalizeRankings(dare: PublicKey, submissions: PublicKey[]) {
+    await program.methods
+      .finalizeRankings()
+      .accounts({ dare })
+      .remainingAccounts(submissions.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+      .rpc();
+  }
+
+  async function selectWinners(dare: PublicKey, [firstPlace, secondPlace, thirdPlace]: (PublicKey | null)[]) {
+    await program.methods
+      .selectWinners()
+      .accounts({
+        dare,
+        firstPlace,
+        secondPlace: secondPlace ?? null,
+        thirdPlace: thirdPlace ?? null,
+        config,
+        platformAuthority: provider.wallet.publicKey,
+      })
+      .rpc();
+  }
+
+  it("Ranks a likes-mode dare by likes once it is completed", async () => {
+    const { dare, submissions } = await dareWithSubmissions("Likes Ranked Dare", { likes: {} });
+
+    // Second submission: 2 likes, first: 1, third: none
+    await likeProof(program, dare, submissions[1], likers[0]);
+    await likeProof(program, dare, submissions[1], likers[1]);
+    await likeProof(program, dare, submissions[0], likers[0]);
+
+    try {
+      await finalizeRankings(dare, submissions);
+      expect.fail("ranked a dare that isn't completed");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidDareStatus");
+    }
+
+    await complete(dare);
+
+    try {
+      await selectWinners(dare, submissions);
+      expect.fail("the admin picked winners on a likes-ranked dare");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RankedByLikes");
+    }
+
+    await finalizeRankings(dare, [...submissions].reverse());
+
+    const ranks = await Promise.all(
+      submissions.map(async (s) => ((await program.account.proofSubmission.fetch(s)) as any).winnerRank)
+    );
+    expect(ranks).to.deep.equal([2, 1, 3]);
+  });
+
+  it("Keeps admin-ranked dares out of finalize_rankings", async () => {
+    const { dare, submissions } = await dareWithSubmissions("Admin Ranked Dare", { admin: {} });
+    await complete(dare);
+
+    try {
+      await finalizeRankings(dare, submissions);
+      expect.fail("ranked an admin-ranked dare by likes");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RankedByAdmin");
+    }
+
+    await selectWinners(dare, submissions);
+    const first = await program.account.proofSubmission.fetch(submissions[0]) as any;
+    expect(first.winnerRank).to.equal(1);
+  });
+
+  it("Binds admin-picked winners to the dare's approved submissions", async () => {
+    const other = await createDare(program, creator, "Other Binding Dare");
+    const foreign = await submitProof(program, other, submitters[0]);
+    await approveProof(program, provider, other, foreign);
+
+    const { dare, submissions } = await dareWithSubmissions("Winner Binding Dare", { admin: {} });
+    const unapproved = await submitProof(program, dare, submitters[0]);
+    await complete(dare);
+
+    const rejects = async (places: PublicKey[], code: string) => {
+      try {
+        await selectWinners(dare, places);
+        expect.fail(`selected ${places.map((p) => p.toString())}`);
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal(code);
+      }
+    };
+    await rejects([foreign, submissions[1], submissions[2]], "SubmissionDareMismatch");
+    await rejects([submissions[0], unapproved, submissions[2]], "ProofNotApproved");
+    await rejects([submissions[0], submissions[1], submissions[0]], "DuplicateWinner");
+
+    await selectWinners(dare, submissions);
+    await rejects(submissions, "WinnersAlreadySelected");
+
+    const dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.firstPlaceWinner.toString()).to.equal(submissions[0].toString());
+    expect(dareAccount.secondPlaceWinner.toString()).to.equal(submissions[1].toString());
+    expect(dareAccount.thirdPlaceWinner.toString()).to.equal(submissions[2].toString());
+    for (const [i, submission] of submissions.entries()) {
+      const account = await program.account.proofSubmission.fetch(submission) as any;
+      expect(account.isWinner).to.be.true;
+      expect(account.winnerRank).to.equal(i + 1);
+    }
+  });
+  it("Leaves places empty when fewer than three submissions were approved", async () => {
+    const { dare, submissions } = await dareWithSubmissions("Two Winner Dare", { admin: {} }, 2);
+    await complete(dare);
+
+    for (const places of [[submissions[0]], [submissions[0], null, submissions[1]], [...submissions, submissions[0]]]) {
+      try {
+        await selectWinners(dare, places);
+        expect.fail(`selected ${places.map((p) => p?.toString())}`);
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("UnfilledWinnerPlace");
+      }
+    }
+
+    await selectWinners(dare, [submissions[1], submissions[0]]);
+
+    const dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.firstPlaceWinner.toString()).to.equal(submissions[1].toString());
+    expect(dareAccount.secondPlaceWinner.toString()).to.equal(submissions[0].toString());
+    expect(dareAccount.thirdPlaceWinner).to.be.null;
+  });
+});
@@ -0,0 +1,176 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  airdrop,
+  betAddress,
+  configAddress,
+  createDare,
+  DISPUTE_WINDOW_SECS,
+  ensureConfig,
+  placeBet,
+  poolAddress,
+  resolveDare,
+  sleep,
+  sleepUntil,
+  submitProof,
+  treasuryAddress,
+} from "./helpers";
+
+describe("refunds", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const config = configAddress(program);
+
+  let creator: Keypair;
+  let bettors: Keypair[];
+
+  before(async () => {
+    creator = Keypair.generate();
+    bettors = [Keypair.generate(), Keypair.generate()];
+
+    await airdrop(provider, creator, ...bettors);
+    await ensureConfig(program, provider);
+  });
+
+  async function claimRefund(dare: PublicKey, bettor: Keypair) {
+    await program.methods
+      .claimRefund()
+      .accounts({
+        dare,
+        bet: betAddress(program, dare, bettor.publicKey),
+        poolAccount: poolAddress(program, dare),
+        config,
+        treasury: treasuryAddress(program),
+        bettor: bettor.publicKey,
+      })
+      .signers([bettor])
+      .rpc();
+  }
+
+  /** Claims `bettor`'s refund and returns how much it paid */
+  async function refunded(dare: PublicKey, bettor: Keypair) {
+    const before = await provider.connection.getBalance(bettor.publicKey);
+    await claimRefund(dare, bettor);
+    return (await provider.connection.getBalance(bettor.publicKey)) - before;
+  }
+
+  async function cancelDare(dare: PublicKey, authority: Keypair | null) {
+    await program.methods
+      .cancelDare()
+      .accounts({
+        dare,
+        poolAccount: poolAddress(program, dare),
+        config,
+        creator: creator.publicKey,
+        authority: authority ? authority.publicKey : provider.wallet.publicKey,
+      })
+      .signers(authority ? [authority] : [])
+      .rpc();
+  }
+
+  it("Refunds everyone when nobody bet on the winning side", async () => {
+    const dare = await createDare(program, creator, "One-Sided Dare", { bettingSecs: 4 });
+    await placeBet(program, dare, bettors[0], LAMPORTS_PER_SOL, "willDo");
+    await placeBet(program, dare, bettors[1], LAMPORTS_PER_SOL / 2, "willDo");
+
+    const { proofDeadline } = await program.account.dare.fetch(dare) as any;
+    await sleepUntil(proofDeadline);
+    await resolveDare(program, provider, dare, "wontDo");
+
+    const dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.status).to.deep.equal({ refunding: {} });
+    expect(dareAccount.outcome).to.deep.equal({ wontDo: {} });
+
+    try {
+      await claimRefund(dare, bettors[0]);
+      expect.fail("refunded during the dispute window");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("DisputeWindowOpen");
+    }
+    await sleep((DISPUTE_WINDOW_SECS + 1) * 1000);
+
+    // The fixture's refund fee is 0: stakes come back whole
+    expect(await refunded(dare, bettors[0])).to.equal(LAMPORTS_PER_SOL);
+    expect(await refunded(dare, bettors[1])).to.equal(LAMPORTS_PER_SOL / 2);
+
+    try {
+      await claimRefund(dare, bettors[0]);
+      expect.fail("refunded twice");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AlreadyClaimed");
+    }
+    expect((await program.account.dare.fetch(dare) as any).refundedStake.toNumber()).to.equal(
+      1.5 * LAMPORTS_PER_SOL
+    );
+  });
+
+  it("Lets the creator close a dare nobody has bet on", async () => {
+    const dare = await createDare(program, creator, "Withdrawn Dare");
+
+    try {
+      await cancelDare(dare, bettors[0]);
+      expect.fail("an outsider cancelled the dare");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    // Both accounts are closed and their rent goes back to the creator
+    const rent =
+      (await provider.connection.getBalance(dare)) +
+      (await provider.connection.getBalance(poolAddress(program, dare)));
+    const before = await provider.connection.getBalance(creator.publicKey);
+    await cancelDare(dare, creator);
+    const after = await provider.connection.getBalance(creator.publicKey);
+
+    expect(after - before).to.equal(rent);
+    expect(await program.account.dare.fetchNullable(dare)).to.be.null;
+    expect(await provider.connection.getAccountInfo(poolAddress(program, dare))).to.be.null;
+  });
+
+  it("Keeps an empty dare with proofs as cancelled instead of closing it", async () => {
+    const title = "Proven Withdrawn Dare";
+    const dare = await createDare(program, creator, title);
+    await submitProof(program, dare, bettors[0]);
+
+    // Closing would let the creator reuse the address next to the old proof
+    await cancelDare(dare, creator);
+    expect((await program.account.dare.fetch(dare) as any).status).to.deep.equal({ cancelled: {} });
+
+    try {
+      await createDare(program, creator, title);
+      expect.fail("re-created a cancelled dare");
+    } catch (err: any) {
+      expect(err.toString()).to.include("already in use");
+    }
+  });
+
+  it("Leaves cancelling a dare with bets to the admin, and refunds it", async () => {
+    const dare = await createDare(program, creator, "Cancelled Dare");
+    await placeBet(program, dare, bettors[0], LAMPORTS_PER_SOL, "wontDo");
+
+    try {
+      await cancelDare(dare, creator);
+      expect.fail("the creator cancelled a dare with money in it");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("CancelRequiresAdmin");
+    }
+
+    await cancelDare(dare, null);
+    expect((await program.account.dare.fetch(dare) as any).status).to.deep.equal({ cancelled: {} });
+
+    try {
+      await placeBet(program, dare, bettors[1], LAMPORTS_PER_SOL, "willDo");
+      expect.fail("bet on a cancelled dare");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidDareStatus");
+    }
+
+    // A cancellation can't be disputed: refunds are open right away
+    expect(await refunded(dare, bettors[0])).to.equal(LAMPORTS_PER_SOL);
+  });
+});
@@ -0,0 +1,69 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  airdrop,
+  configAddress,
+  createDare,
+  ensureConfig,
+  placeBet,
+  sleepUntil,
+} from "./helpers";
+
+describe("resolution", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const config = configAddress(program);
+
+  let creator: Keypair;
+  let bettor: Keypair;
+
+  before(async () => {
+    creator = Keypair.generate();
+    bettor = Keypair.generate();
+
+    await airdrop(provider, creator, bettor);
+    await ensureConfig(program, provider);
+  });
+
+  async function resolveAs(dare: PublicKey, resolver: Keypair, outcome: "willDo" | "wontDo") {
+    await program.methods
+      .resolveDare({ [outcome]: {} })
+      .accounts({ dare, config, oracle: null, resolver: resolver.publicKey })
+      .signers([resolver])
+      .rpc();
+  }
+
+  it("Leaves resolution to the admin until the resolution deadline, then settles on won't do", async () => {
+    const dare = await createDare(program, creator, "Unresolved Dare", { bettingSecs: 4 });
+    await placeBet(program, dare, bettor, LAMPORTS_PER_SOL, "willDo");
+
+    try {
+      await resolveAs(dare, bettor, "willDo");
+      expect.fail("resolved before the proof deadline");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidDareStatus");
+    }
+
+    const { proofDeadline, resolutionDeadline } = await program.account.dare.fetch(dare) as any;
+    await sleepUntil(proofDeadline);
+
+    try {
+      await resolveAs(dare, bettor, "willDo");
+      expect.fail("a bettor resolved an admin dare");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    // Permissionless once the admin has let the deadline pass, whatever outcome is asked for
+    await sleepUntil(resolutionDeadline);
+    await resolveAs(dare, bettor, "willDo");
+
+    const dareAccount = await program.account.dare.fetch(dare) as any;
+    expect(dareAccount.outcome).to.deep.equal({ wontDo: {} });
+  });
+});
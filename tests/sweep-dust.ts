@@ -0,0 +1,117 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  airdrop,
+  betAddress,
+  configAddress,
+  createDare,
+  ensureConfig,
+  placeBet,
+  poolAddress,
+  resolveDare,
+  sleepUntil,
+  treasuryAddress,
+} from "./helpers";
+
+// Short enough to wait out in a test
+const CLAIM_WINDOW_SECS = 8;
+
+describe("sweep-dust", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const config = configAddress(program);
+  const treasury = treasuryAddress(program);
+
+  let creator: Keypair;
+  let winner: Keypair;
+  let loser: Keypair;
+
+  before(async () => {
+    creator = Keypair.generate();
+    winner = Keypair.generate();
+    loser = Keypair.generate();
+
+    await airdrop(provider, creator, winner, loser);
+    await ensureConfig(program, provider);
+  });
+
+  async function setClaimWindow(secs: number | anchor.BN) {
+    await program.methods
+      .setClaimWindow(new anchor.BN(secs))
+      .accounts({ config, admin: provider.wallet.publicKey })
+      .rpc();
+  }
+
+  async function sweepDust(dare: PublicKey) {
+    await program.methods
+      .sweepDust()
+      .accounts({ dare, poolAccount: poolAddress(program, dare), config, treasury })
+      .rpc();
+  }
+
+  it("Sweeps unclaimed payouts into the treasury once the claim window has passed", async () => {
+    const { claimWindowSecs } = await program.account.programConfig.fetch(config) as any;
+    await setClaimWindow(CLAIM_WINDOW_SECS);
+    try {
+      const dare = await createDare(program, creator, "Unclaimed Dare", { bettingSecs: 4 });
+      await placeBet(program, dare, winner, LAMPORTS_PER_SOL, "willDo");
+      await placeBet(program, dare, loser, LAMPORTS_PER_SOL, "wontDo");
+
+      const { proofDeadline } = await program.account.dare.fetch(dare) as any;
+      await sleepUntil(proofDeadline);
+      await resolveDare(program, provider, dare, "willDo");
+      const { disputeDeadline } = await program.account.dare.fetch(dare) as any;
+      await sleepUntil(disputeDeadline);
+
+      // The winner hasn't claimed yet and still has time to
+      try {
+        await sweepDust(dare);
+        expect.fail("swept during the claim window");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ClaimsOutstanding");
+      }
+
+      await sleepUntil(disputeDeadline.toNumber() + CLAIM_WINDOW_SECS);
+
+      // Everything above the pool's rent-exempt minimum is forfeited
+      const pool = poolAddress(program, dare);
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(
+        (await provider.connection.getAccountInfo(pool))!.data.length
+      );
+      const leftover = (await provider.connection.getBalance(pool)) - rent;
+      const before = await provider.connection.getBalance(treasury);
+      await sweepDust(dare);
+      const after = await provider.connection.getBalance(treasury);
+
+      expect(leftover).to.be.greaterThan(0);
+      expect(after - before).to.equal(leftover);
+      expect(await provider.connection.getBalance(pool)).to.equal(rent);
+      expect((await program.account.dare.fetch(dare) as any).status).to.deep.equal({ closed: {} });
+
+      try {
+        await program.methods
+          .claimWinnings()
+          .accounts({
+            dare,
+            bet: betAddress(program, dare, winner.publicKey),
+            poolAccount: pool,
+            config,
+            winner: winner.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([winner])
+          .rpc();
+        expect.fail("claimed from a closed dare");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidDareStatus");
+      }
+    } finally {
+      await setClaimWindow(claimWindowSecs);
+    }
+  });
+});
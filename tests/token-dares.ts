@@ -0,0 +1,429 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  LAMPORTS_PER_SOL,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  createMint,
+  createAccount,
+  mintTo,
+  getAccount,
+} from "@solana/spl-token";
+import {
+  airdrop,
+  allowedMintAddress,
+  betAddress,
+  configAddress,
+  cpiEvents,
+  dareAddress,
+  DISPUTE_WINDOW_SECS,
+  ensureConfig,
+  FEES,
+  poolAddress,
+  resolveDare,
+  sleep,
+  sleepUntil,
+  treasuryAddress,
+  vaultAddress,
+} from "./helpers";
+
+describe("token dares", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.DareBetting as Program<any>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const config = configAddress(program);
+  const treasury = treasuryAddress(program);
+
+  let creator: Keypair;
+  let bettor: Keypair;
+  let otherBettor: Keypair;
+  let resolutionWindowSecs: number;
+
+  before(async () => {
+    creator = Keypair.generate();
+    bettor = Keypair.generate();
+    otherBettor = Keypair.generate();
+
+    await airdrop(provider, creator, bettor, otherBettor);
+
+    const existing = await ensureConfig(program, provider);
+    resolutionWindowSecs = existing.resolutionWindowSecs.toNumber();
+  });
+
+  /** Creates a dare in a fresh 6-decimal mint, open for `bettingSecs` (an hour by default) */
+  async function createTokenDare(
+    title: string,
+    tokenProgram: PublicKey,
+    { allow = true, bettingSecs = 3600 }: { allow?: boolean; bettingSecs?: number } = {}
+  ) {
+    // A 6-decimal mint standing in for USDC
+    const mint = await createMint(
+      provider.connection,
+      creator,
+      creator.publicKey,
+      null,
+      6,
+      undefined,
+      undefined,
+      tokenProgram
+    );
+    const allowedMint = allowedMintAddress(program, mint);
+
+    if (allow) {
+      // 1 to 100 tokens per position
+      await program.methods
+        .setAllowedMint(6, new anchor.BN(1_000_000), new anchor.BN(100_000_000), null)
+        .accounts({
+          config,
+          mint,
+          allowedMint,
+          admin: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    const now = Math.floor(Date.now() / 1000);
+    const schedule = {
+      bettingClosesAt: new anchor.BN(now + bettingSecs),
+      proofDeadline: new anchor.BN(now + bettingSecs),
+      resolutionDeadline: new anchor.BN(now + bettingSecs + resolutionWindowSecs),
+    };
+
+    const dare = dareAddress(program, creator.publicKey, title);
+    const poolAccount = poolAddress(program, dare);
+    const vault = vaultAddress(program, dare);
+
+    await program.methods
+      .createDare(title, "Staked in tokens", schedule, new anchor.BN(1_000_000), null, { admin: {} }, { admin: {} })
+      .accounts({
+        dare,
+        poolAccount,
+        mint,
+        vault,
+        allowedMint: allow ? allowedMint : null,
+        config,
+        creator: creator.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([creator])
+      .rpc();
+
+    return { mint, dare, poolAccount, vault, allowedMint, tokenProgram };
+  }
+
+  type TokenDare = Awaited<ReturnType<typeof createTokenDare>>;
+
+  /** A token account for `owner`, funded with `amount` of `mint` */
+  async function tokenAccount(mint: PublicKey, owner: PublicKey, tokenProgram: PublicKey, amount = 0) {
+    // A keypair account rather than an ATA, as the treasury PDA is off-curve
+    const account = await createAccount(
+      provider.connection, bettor, mint, owner, Keypair.generate(), undefined, tokenProgram
+    );
+    if (amount > 0) {
+      await mintTo(provider.connection, creator, mint, account, creator, amount, [], undefined, tokenProgram);
+    }
+    return account;
+  }
+
+  async function tokenBalance(account: PublicKey, tokenProgram: PublicKey) {
+    return Number((await getAccount(provider.connection, account, undefined, tokenProgram)).amount);
+  }
+
+  async function placeTokenBet(
+    { dare, poolAccount, mint, vault, allowedMint, tokenProgram }: TokenDare,
+    bettor: Keypair,
+    bettorTokens: PublicKey,
+    amount: number,
+    side: "willDo" | "wontDo"
+  ) {
+    await program.methods
+      .placeBet(new anchor.BN(amount), { [side]: {} })
+      .accounts({
+        dare,
+        bet: betAddress(program, dare, bettor.publicKey),
+        poolAccount,
+        mint,
+        vault,
+        tokenProgram,
+        bettorTokens,
+        allowedMint,
+        config,
+        bettor: bettor.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bettor])
+      .rpc();
+  }
+
+  async function sweepDust({ dare, poolAccount, mint, vault, tokenProgram }: TokenDare, treasuryTokens: PublicKey) {
+    await program.methods
+      .sweepDust()
+      .accounts({ dare, poolAccount, mint, vault, tokenProgram, treasuryTokens, config, treasury })
+      .rpc();
+  }
+
+  /** Waits out the proof deadline, resolves `dare` as `outcome` and waits out the dispute window */
+  async function settle(dare: PublicKey, outcome: "willDo" | "wontDo") {
+    const { proofDeadline } = await program.account.dare.fetch(dare) as any;
+    await sleepUntil(proofDeadline);
+    await resolveDare(program, provider, dare, outcome);
+    await sleep((DISPUTE_WINDOW_SECS + 1) * 1000);
+  }
+
+  for (const [name, tokenProgram] of [
+    ["SPL Token", TOKEN_PROGRAM_ID],
+    ["Token-2022", TOKEN_2022_PROGRAM_ID],
+  ] as const) {
+    it(`Takes ${name} bets into the dare's vault and cashes them out`, async () => {
+      const { mint, dare, poolAccount, vault, allowedMint } = await createTokenDare(
+        `${name} Dare`,
+        tokenProgram
+      );
+
+      const dareAccount = await program.account.dare.fetch(dare) as any;
+      expect(dareAccount.mint.toString()).to.equal(mint.toString());
+
+      const bettorTokens = await createAccount(
+        provider.connection, bettor, mint, bettor.publicKey, undefined, undefined, tokenProgram
+      );
+      const treasuryTokens = await createAccount(
+        provider.connection, bettor, mint, treasury, Keypair.generate(), undefined, tokenProgram
+      );
+      await mintTo(
+        provider.connection, creator, mint, bettorTokens, creator, 10_000_000, [], undefined, tokenProgram
+      );
+
+      const bet = betAddress(program, dare, bettor.publicKey);
+      const signature = await program.methods
+        .placeBet(new anchor.BN(5_000_000), { willDo: {} })
+        .accounts({
+          dare,
+          bet,
+          poolAccount,
+          mint,
+          vault,
+          tokenProgram,
+          bettorTokens,
+          allowedMint,
+          config,
+          bettor: bettor.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bettor])
+        .rpc({ commitment: "confirmed" });
+
+      const [betPlaced] = await cpiEvents(program, provider, signature);
+      expect(betPlaced.name).to.equal("BetPlaced");
+      expect(betPlaced.data.bettor.toString()).to.equal(bettor.publicKey.toString());
+      expect(betPlaced.data.amount.toString()).to.equal("5000000");
+      expect(betPlaced.data.totalPool.toString()).to.equal("5000000");
+
+      let vaultAccount = await getAccount(provider.connection, vault, undefined, tokenProgram);
+      expect(vaultAccount.amount.toString()).to.equal("5000000");
+
+      await program.methods
+        .cashOutEarly()
+        .accounts({
+          dare,
+          bet,
+          poolAccount,
+          mint,
+          vault,
+          tokenProgram,
+          bettorTokens,
+          treasuryTokens,
+          config,
+          treasury,
+          bettor: bettor.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bettor])
+        .rpc();
+
+      // 10% penalty: half stays in the vault, half goes to the treasury
+      const bettorAccount = await getAccount(provider.connection, bettorTokens, undefined, tokenProgram);
+      const treasuryAccount = await getAccount(provider.connection, treasuryTokens, undefined, tokenProgram);
+      vaultAccount = await getAccount(provider.connection, vault, undefined, tokenProgram);
+      expect(bettorAccount.amount.toString()).to.equal("9500000");
+      expect(treasuryAccount.amount.toString()).to.equal("250000");
+      expect(vaultAccount.amount.toString()).to.equal("250000");
+    });
+
+    it(`Pays winnings and fees out of a ${name} vault`, async () => {
+      const tokenDare = await createTokenDare(`${name} Payout Dare`, tokenProgram, { bettingSecs: 8 });
+      const { mint, dare, poolAccount, vault } = tokenDare;
+
+      const bettorTokens = await tokenAccount(mint, bettor.publicKey, tokenProgram, 10_000_000);
+      const otherTokens = await tokenAccount(mint, otherBettor.publicKey, tokenProgram, 10_000_000);
+      const creatorTokens = await tokenAccount(mint, creator.publicKey, tokenProgram);
+      const treasuryTokens = await tokenAccount(mint, treasury, tokenProgram);
+
+      await placeTokenBet(tokenDare, bettor, bettorTokens, 8_000_000, "wontDo");
+      await placeTokenBet(tokenDare, otherBettor, otherTokens, 2_000_000, "willDo");
+      await settle(dare, "wontDo");
+
+      // The only winner gets the whole pool but the creator and protocol fees
+      const totalPool = 10_000_000;
+      const creatorFee = (totalPool * FEES.creatorFeeBps) / 10000;
+      const protocolFee = (totalPool * FEES.protocolFeeBps) / 10000;
+
+      await program.methods
+        .claimWinnings()
+        .accounts({
+          dare,
+          bet: betAddress(program, dare, bettor.publicKey),
+          poolAccount,
+          mint,
+          vault,
+          tokenProgram,
+          winnerTokens: bettorTokens,
+          config,
+          winner: bettor.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bettor])
+        .rpc();
+      expect(await tokenBalance(bettorTokens, tokenProgram)).to.equal(
+        2_000_000 + totalPool - creatorFee - protocolFee
+      );
+
+      await program.methods
+        .claimCreatorFee()
+        .accounts({
+          dare,
+          poolAccount,
+          mint,
+          vault,
+          tokenProgram,
+          creatorTokens,
+          config,
+          creator: creator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+      expect(await tokenBalance(creatorTokens, tokenProgram)).to.equal(creatorFee);
+
+      await program.methods
+        .collectProtocolFee()
+        .accounts({ dare, poolAccount, mint, vault, tokenProgram, treasuryTokens, config, treasury })
+        .rpc();
+      expect(await tokenBalance(treasuryTokens, tokenProgram)).to.equal(protocolFee);
+
+      // Everything owed has been paid, so the sweep doesn't wait for the claim window
+      await sweepDust(tokenDare, treasuryTokens);
+      expect(await tokenBalance(vault, tokenProgram)).to.equal(0);
+      expect((await program.account.dare.fetch(dare) as any).status).to.deep.equal({ closed: {} });
+    });
+
+    it(`Refunds ${name} bets and sweeps the cash-out penalty left behind`, async () => {
+      const tokenDare = await createTokenDare(`${name} Refund Dare`, tokenProgram, { bettingSecs: 8 });
+      const { mint, dare, poolAccount, vault } = tokenDare;
+
+      const bettorTokens = await tokenAccount(mint, bettor.publicKey, tokenProgram, 10_000_000);
+      const otherTokens = await tokenAccount(mint, otherBettor.publicKey, tokenProgram, 10_000_000);
+      const treasuryTokens = await tokenAccount(mint, treasury, tokenProgram);
+
+      await placeTokenBet(tokenDare, bettor, bettorTokens, 4_000_000, "willDo");
+      await placeTokenBet(tokenDare, otherBettor, otherTokens, 2_000_000, "wontDo");
+
+      // Nobody is left on "won't do": 10% penalty, half kept in the vault
+      await program.methods
+        .cashOutEarly()
+        .accounts({
+          dare,
+          bet: betAddress(program, dare, otherBettor.publicKey),
+          poolAccount,
+          mint,
+          vault,
+          tokenProgram,
+          bettorTokens: otherTokens,
+          treasuryTokens,
+          config,
+          treasury,
+          bettor: otherBettor.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([otherBettor])
+        .rpc();
+      expect(await tokenBalance(treasuryTokens, tokenProgram)).to.equal(100_000);
+
+      await settle(dare, "wontDo");
+      expect((await program.account.dare.fetch(dare) as any).status).to.deep.equal({ refunding: {} });
+
+      await program.methods
+        .claimRefund()
+        .accounts({
+          dare,
+          bet: betAddress(program, dare, bettor.publicKey),
+          poolAccount,
+          mint,
+          vault,
+          tokenProgram,
+          bettorTokens,
+          treasuryTokens,
+          config,
+          treasury,
+          bettor: bettor.publicKey,
+        })
+        .signers([bettor])
+        .rpc();
+      const refundFee = (4_000_000 * FEES.refundFeeBps) / 10000;
+      expect(await tokenBalance(bettorTokens, tokenProgram)).to.equal(10_000_000 - refundFee);
+
+      // The pool's share of the penalty belongs to nobody once every stake is refunded
+      await sweepDust(tokenDare, treasuryTokens);
+      expect(await tokenBalance(treasuryTokens, tokenProgram)).to.equal(200_000 + refundFee);
+      expect(await tokenBalance(vault, tokenProgram)).to.equal(0);
+    });
+  }
+
+  it("Rejects a token bet without the token accounts", async () => {
+    const { dare, poolAccount } = await createTokenDare("Token Dare Missing Accounts", TOKEN_PROGRAM_ID);
+
+    const bet = betAddress(program, dare, bettor.publicKey);
+    try {
+      await program.methods
+        .placeBet(new anchor.BN(LAMPORTS_PER_SOL), { willDo: {} })
+        .accounts({
+          dare,
+          bet,
+          poolAccount,
+          mint: null,
+          vault: null,
+          tokenProgram: null,
+          bettorTokens: null,
+          allowedMint: null,
+          config,
+          bettor: bettor.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bettor])
+        .rpc();
+      expect.fail("bet SOL on a token dare");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("MissingTokenAccounts");
+    }
+  });
+
+  it("Rejects dares in mints that are not on the allowlist", async () => {
+    try {
+      await createTokenDare("Unlisted Token Dare", TOKEN_PROGRAM_ID, { allow: false });
+      expect.fail("created a dare in an unlisted mint");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("MintNotAllowed");
+    }
+  });
+});